@@ -1,17 +1,466 @@
 use std::{fmt::Debug, hash::Hash};
 
-pub trait Constraints: Debug + Clone + PartialEq + Hash {}
+use crate::geometry::{EdgeInsets, Size};
 
-#[derive(Debug, Clone, Copy, PartialEq, Hash)]
-pub struct BoxConstraints {}
+pub trait Constraints: Debug + Clone + PartialEq + Hash {
+    /// Whether these constraints leave no freedom, i.e. whether a render
+    /// object subject to them is necessarily a relayout boundary regardless
+    /// of `parent_uses_size`.
+    fn is_tight(&self) -> bool {
+        false
+    }
+}
+
+/// Immutable layout constraints for a [RenderBox](crate::rendering::RenderBox).
+///
+/// A box's parent gives it a range of acceptable widths and a range of
+/// acceptable heights; the box must then choose a [Size] within those
+/// ranges. The invariant that always holds is
+/// `0.0 <= min_width <= max_width` (and likewise for height), where either
+/// `max` may be [f32::INFINITY] to mean "no upper bound".
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BoxConstraints {
+    pub min_width: f32,
+    pub max_width: f32,
+    pub min_height: f32,
+    pub max_height: f32,
+}
+
+impl Hash for BoxConstraints {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.min_width.to_bits().hash(state);
+        self.max_width.to_bits().hash(state);
+        self.min_height.to_bits().hash(state);
+        self.max_height.to_bits().hash(state);
+    }
+}
+
+impl Constraints for BoxConstraints {
+    fn is_tight(&self) -> bool {
+        BoxConstraints::is_tight(self)
+    }
+}
+
+impl BoxConstraints {
+    /// Creates box constraints with the given ranges.
+    ///
+    /// Panics (in debug builds) if the invariant
+    /// `0.0 <= min <= max` is violated for either dimension.
+    pub fn new(min_width: f32, max_width: f32, min_height: f32, max_height: f32) -> Self {
+        debug_assert!(0.0 <= min_width && min_width <= max_width);
+        debug_assert!(0.0 <= min_height && min_height <= max_height);
+        BoxConstraints {
+            min_width,
+            max_width,
+            min_height,
+            max_height,
+        }
+    }
+
+    /// Constraints that require the given exact size.
+    pub fn tight(size: Size) -> Self {
+        BoxConstraints::new(size.width, size.width, size.height, size.height)
+    }
+
+    /// Constraints that require a size no bigger than the given size.
+    pub fn loose(size: Size) -> Self {
+        BoxConstraints::new(0.0, size.width, 0.0, size.height)
+    }
+
+    /// Constraints that require the given exact width and/or height, leaving
+    /// the other dimension unconstrained where `None` is passed.
+    pub fn tight_for(width: Option<f32>, height: Option<f32>) -> Self {
+        BoxConstraints::new(
+            width.unwrap_or(0.0),
+            width.unwrap_or(f32::INFINITY),
+            height.unwrap_or(0.0),
+            height.unwrap_or(f32::INFINITY),
+        )
+    }
+
+    /// Constraints that expand to fill another box's constraints, i.e. the
+    /// tightest constraints that still respect the given exact width and/or
+    /// height (or fill all available space for a dimension left as `None`).
+    pub fn expand(width: Option<f32>, height: Option<f32>) -> Self {
+        BoxConstraints::new(
+            width.unwrap_or(f32::INFINITY),
+            width.unwrap_or(f32::INFINITY),
+            height.unwrap_or(f32::INFINITY),
+            height.unwrap_or(f32::INFINITY),
+        )
+    }
+
+    /// Returns the size that both satisfies these constraints and is as
+    /// close as possible to the given size.
+    pub fn constrain(&self, size: Size) -> Size {
+        Size::new(
+            self.constrain_width(size.width),
+            self.constrain_height(size.height),
+        )
+    }
+
+    /// Returns the width that both satisfies these constraints and is as
+    /// close as possible to the given width.
+    pub fn constrain_width(&self, width: f32) -> f32 {
+        width.clamp(self.min_width, self.max_width)
+    }
+
+    /// Returns the height that both satisfies these constraints and is as
+    /// close as possible to the given height.
+    pub fn constrain_height(&self, height: f32) -> f32 {
+        height.clamp(self.min_height, self.max_height)
+    }
+
+    /// Returns constraints that are as restrictive as both `self` and
+    /// `other` at once, i.e. the intersection of the two ranges for each
+    /// dimension.
+    pub fn enforce(&self, other: &BoxConstraints) -> BoxConstraints {
+        BoxConstraints::new(
+            self.min_width.clamp(other.min_width, other.max_width),
+            self.max_width.clamp(other.min_width, other.max_width),
+            self.min_height.clamp(other.min_height, other.max_height),
+            self.max_height.clamp(other.min_height, other.max_height),
+        )
+    }
+
+    /// Returns new box constraints that are smaller by the given edge
+    /// insets, as if a child were being laid out inside padding.
+    pub fn deflate(&self, insets: EdgeInsets) -> BoxConstraints {
+        let horizontal = insets.horizontal();
+        let vertical = insets.vertical();
+        let min_width = (self.min_width - horizontal).max(0.0);
+        let min_height = (self.min_height - vertical).max(0.0);
+        BoxConstraints::new(
+            min_width,
+            (self.max_width - horizontal).max(min_width),
+            min_height,
+            (self.max_height - vertical).max(min_height),
+        )
+    }
+
+    /// Whether there is exactly one size that satisfies these constraints.
+    pub fn is_tight(&self) -> bool {
+        self.min_width == self.max_width && self.min_height == self.max_height
+    }
+
+    /// Whether there is an upper bound on the width.
+    pub fn has_bounded_width(&self) -> bool {
+        self.max_width < f32::INFINITY
+    }
+
+    /// Whether there is an upper bound on the height.
+    pub fn has_bounded_height(&self) -> bool {
+        self.max_height < f32::INFINITY
+    }
+
+    /// The biggest size that satisfies these constraints.
+    pub fn biggest(&self) -> Size {
+        Size::new(self.max_width, self.max_height)
+    }
+
+    /// The smallest size that satisfies these constraints.
+    pub fn smallest(&self) -> Size {
+        Size::new(self.min_width, self.min_height)
+    }
+}
+
+/// The main axis a [RenderSliver](crate::rendering::RenderSliver) scrolls
+/// along.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Axis {
+    Horizontal,
+    Vertical,
+}
+
+/// The four concrete directions an [Axis] can point, i.e. which way
+/// increasing scroll offset moves content.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum AxisDirection {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl AxisDirection {
+    /// The [Axis] this direction moves along.
+    pub fn axis(self) -> Axis {
+        match self {
+            AxisDirection::Up | AxisDirection::Down => Axis::Vertical,
+            AxisDirection::Left | AxisDirection::Right => Axis::Horizontal,
+        }
+    }
+}
 
-impl Constraints for BoxConstraints {}
+/// Whether a sliver's contents grow away from the viewport's zero scroll
+/// offset ([GrowthDirection::Forward]) or towards it
+/// ([GrowthDirection::Reverse]).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum GrowthDirection {
+    Forward,
+    Reverse,
+}
 
-#[derive(Debug, Clone, Copy, PartialEq, Hash)]
-pub struct SliverConstraints {}
+/// Immutable layout constraints for a
+/// [RenderSliver](crate::rendering::RenderSliver).
+///
+/// Unlike [BoxConstraints], a sliver isn't given a size range to pick from:
+/// it is handed a slice of an already-decided viewport (a cross-axis extent,
+/// how much of the main axis remains to paint and cache, how far it has
+/// already scrolled past) and reports back how much of the scrollable it
+/// occupies via [SliverGeometry]. This non-Cartesian shape is exactly why
+/// [RenderObject](crate::rendering::RenderObject) is generic over
+/// [Constraints] rather than hard-coded to boxes.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SliverConstraints {
+    pub axis_direction: AxisDirection,
+    pub growth_direction: GrowthDirection,
+    /// How far, in logical pixels, this sliver's leading edge has already
+    /// scrolled past the start of the viewport.
+    pub scroll_offset: f32,
+    /// The scroll extent consumed by every sliver before this one.
+    pub preceding_scroll_extent: f32,
+    /// How much main-axis paint extent is still available to this sliver
+    /// and those after it.
+    pub remaining_paint_extent: f32,
+    pub cross_axis_extent: f32,
+    pub viewport_main_axis_extent: f32,
+    /// How much main-axis extent beyond [remaining_paint_extent] is still
+    /// available for off-screen caching.
+    pub remaining_cache_extent: f32,
+}
+
+impl Hash for SliverConstraints {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.axis_direction.hash(state);
+        self.growth_direction.hash(state);
+        self.scroll_offset.to_bits().hash(state);
+        self.preceding_scroll_extent.to_bits().hash(state);
+        self.remaining_paint_extent.to_bits().hash(state);
+        self.cross_axis_extent.to_bits().hash(state);
+        self.viewport_main_axis_extent.to_bits().hash(state);
+        self.remaining_cache_extent.to_bits().hash(state);
+    }
+}
 
 impl Constraints for SliverConstraints {}
 
 impl SliverConstraints {
-    pub fn as_box_constrains(&self) -> BoxConstraints {}
+    /// Projects these constraints into the [BoxConstraints] a sliver would
+    /// hand to an ordinary box child occupying its cross axis: the cross
+    /// axis is tight (the child must fill it exactly), while the main axis
+    /// is left unbounded, since a sliver's main-axis extent is a function of
+    /// its child's content, not the other way around.
+    pub fn as_box_constraints(&self) -> BoxConstraints {
+        match self.axis_direction.axis() {
+            Axis::Horizontal => BoxConstraints::new(
+                0.0,
+                f32::INFINITY,
+                self.cross_axis_extent,
+                self.cross_axis_extent,
+            ),
+            Axis::Vertical => BoxConstraints::new(
+                self.cross_axis_extent,
+                self.cross_axis_extent,
+                0.0,
+                f32::INFINITY,
+            ),
+        }
+    }
+}
+
+/// What a [RenderSliver](crate::rendering::RenderSliver) reports back after
+/// [perform_layout](crate::rendering::RenderSliver::perform_layout), the
+/// sliver-protocol analogue of a box's [Size].
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SliverGeometry {
+    /// How much scrollable main-axis extent this sliver occupies in total,
+    /// including any part that is out of view.
+    pub scroll_extent: f32,
+    /// How much of this sliver is actually visible and should be painted.
+    pub paint_extent: f32,
+    /// How much main-axis space this sliver occupies for the purposes of
+    /// laying out the slivers that follow it; usually equal to
+    /// [paint_extent], but can differ for slivers that overlap their
+    /// successors (e.g. pinned headers).
+    pub layout_extent: f32,
+    /// The furthest [paint_extent] this sliver could ever report, used by
+    /// the viewport to decide how much further scrolling is possible.
+    pub max_paint_extent: f32,
+    /// How much main-axis extent this sliver takes up in the cache area,
+    /// i.e. including content that is off-screen but kept around for
+    /// cheaper scrolling.
+    pub cache_extent: f32,
+    /// Whether this sliver has any visible content at all.
+    pub visible: bool,
+    /// How much of this sliver participates in hit testing; usually equal
+    /// to [paint_extent].
+    pub hit_test_extent: f32,
+}
+
+impl SliverGeometry {
+    /// Builds a geometry from the handful of values most slivers actually
+    /// need to compute, deriving the rest the way Flutter's own
+    /// `SliverGeometry` constructor defaults them: [layout_extent],
+    /// [cache_extent], and [hit_test_extent] default to [paint_extent], and
+    /// [visible] defaults to whether [paint_extent] is positive.
+    pub fn new(scroll_extent: f32, paint_extent: f32, max_paint_extent: f32) -> Self {
+        SliverGeometry {
+            scroll_extent,
+            paint_extent,
+            layout_extent: paint_extent,
+            max_paint_extent,
+            cache_extent: paint_extent,
+            visible: paint_extent > 0.0,
+            hit_test_extent: paint_extent,
+        }
+    }
+
+    /// A sliver that occupies no space and paints nothing.
+    pub fn zero() -> Self {
+        SliverGeometry {
+            scroll_extent: 0.0,
+            paint_extent: 0.0,
+            layout_extent: 0.0,
+            max_paint_extent: 0.0,
+            cache_extent: 0.0,
+            visible: false,
+            hit_test_extent: 0.0,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn constrain_clamps_into_range() {
+        let constraints = BoxConstraints::new(10.0, 100.0, 20.0, 200.0);
+        assert_eq!(constraints.constrain(Size::new(5.0, 5.0)), Size::new(10.0, 20.0));
+        assert_eq!(
+            constraints.constrain(Size::new(500.0, 500.0)),
+            Size::new(100.0, 200.0)
+        );
+        assert_eq!(constraints.constrain(Size::new(50.0, 50.0)), Size::new(50.0, 50.0));
+    }
+
+    #[test]
+    fn tight_for_leaves_unspecified_dimension_unbounded() {
+        let constraints = BoxConstraints::tight_for(Some(50.0), None);
+        assert_eq!(constraints.min_width, 50.0);
+        assert_eq!(constraints.max_width, 50.0);
+        assert_eq!(constraints.min_height, 0.0);
+        assert_eq!(constraints.max_height, f32::INFINITY);
+    }
+
+    #[test]
+    fn expand_fills_unspecified_dimension() {
+        let constraints = BoxConstraints::expand(Some(50.0), None);
+        assert_eq!(constraints.min_width, 50.0);
+        assert_eq!(constraints.max_width, 50.0);
+        assert_eq!(constraints.min_height, f32::INFINITY);
+        assert_eq!(constraints.max_height, f32::INFINITY);
+    }
+
+    #[test]
+    fn enforce_intersects_both_ranges() {
+        let outer = BoxConstraints::new(0.0, 100.0, 0.0, 100.0);
+        let inner = BoxConstraints::new(50.0, 200.0, 0.0, 30.0);
+        let enforced = inner.enforce(&outer);
+        assert_eq!(enforced, BoxConstraints::new(50.0, 100.0, 0.0, 30.0));
+    }
+
+    #[test]
+    fn deflate_shrinks_by_insets_without_going_negative() {
+        let constraints = BoxConstraints::new(0.0, 100.0, 0.0, 100.0);
+        let deflated = constraints.deflate(EdgeInsets::all(60.0));
+        // 100 - 120 would be negative; both min and max clamp to 0.
+        assert_eq!(deflated, BoxConstraints::new(0.0, 0.0, 0.0, 0.0));
+
+        let deflated = constraints.deflate(EdgeInsets::symmetric(10.0, 5.0));
+        assert_eq!(deflated, BoxConstraints::new(0.0, 80.0, 0.0, 90.0));
+    }
+
+    #[test]
+    fn is_tight_requires_exact_size() {
+        assert!(BoxConstraints::tight(Size::new(10.0, 10.0)).is_tight());
+        assert!(!BoxConstraints::loose(Size::new(10.0, 10.0)).is_tight());
+    }
+
+    #[test]
+    fn bounded_reflects_finite_max() {
+        let bounded = BoxConstraints::new(0.0, 100.0, 0.0, 100.0);
+        let unbounded = BoxConstraints::expand(None, None);
+        assert!(bounded.has_bounded_width());
+        assert!(bounded.has_bounded_height());
+        assert!(!unbounded.has_bounded_width());
+        assert!(!unbounded.has_bounded_height());
+    }
+
+    #[test]
+    fn biggest_and_smallest_are_the_extremes() {
+        let constraints = BoxConstraints::new(10.0, 100.0, 20.0, 200.0);
+        assert_eq!(constraints.biggest(), Size::new(100.0, 200.0));
+        assert_eq!(constraints.smallest(), Size::new(10.0, 20.0));
+    }
+
+    #[test]
+    fn axis_direction_axis_matches_orientation() {
+        assert_eq!(AxisDirection::Up.axis(), Axis::Vertical);
+        assert_eq!(AxisDirection::Down.axis(), Axis::Vertical);
+        assert_eq!(AxisDirection::Left.axis(), Axis::Horizontal);
+        assert_eq!(AxisDirection::Right.axis(), Axis::Horizontal);
+    }
+
+    fn sliver_constraints(axis_direction: AxisDirection, cross_axis_extent: f32) -> SliverConstraints {
+        SliverConstraints {
+            axis_direction,
+            growth_direction: GrowthDirection::Forward,
+            scroll_offset: 0.0,
+            preceding_scroll_extent: 0.0,
+            remaining_paint_extent: 500.0,
+            cross_axis_extent,
+            viewport_main_axis_extent: 500.0,
+            remaining_cache_extent: 500.0,
+        }
+    }
+
+    #[test]
+    fn sliver_as_box_constraints_ties_cross_axis_and_frees_main_axis_when_vertical() {
+        let box_constraints = sliver_constraints(AxisDirection::Down, 300.0).as_box_constraints();
+        assert_eq!(box_constraints.min_width, 300.0);
+        assert_eq!(box_constraints.max_width, 300.0);
+        assert_eq!(box_constraints.min_height, 0.0);
+        assert_eq!(box_constraints.max_height, f32::INFINITY);
+    }
+
+    #[test]
+    fn sliver_as_box_constraints_ties_cross_axis_and_frees_main_axis_when_horizontal() {
+        let box_constraints = sliver_constraints(AxisDirection::Right, 300.0).as_box_constraints();
+        assert_eq!(box_constraints.min_height, 300.0);
+        assert_eq!(box_constraints.max_height, 300.0);
+        assert_eq!(box_constraints.min_width, 0.0);
+        assert_eq!(box_constraints.max_width, f32::INFINITY);
+    }
+
+    #[test]
+    fn sliver_geometry_new_defaults_from_paint_extent() {
+        let geometry = SliverGeometry::new(500.0, 100.0, 500.0);
+        assert_eq!(geometry.layout_extent, 100.0);
+        assert_eq!(geometry.cache_extent, 100.0);
+        assert_eq!(geometry.hit_test_extent, 100.0);
+        assert!(geometry.visible);
+
+        let empty = SliverGeometry::new(500.0, 0.0, 500.0);
+        assert!(!empty.visible);
+    }
+
+    #[test]
+    fn sliver_geometry_zero_occupies_nothing() {
+        let zero = SliverGeometry::zero();
+        assert_eq!(zero.scroll_extent, 0.0);
+        assert_eq!(zero.paint_extent, 0.0);
+        assert!(!zero.visible);
+    }
 }
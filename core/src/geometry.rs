@@ -0,0 +1,294 @@
+//! Basic 2-D geometry primitives shared by the rendering protocol.
+//!
+//! These mirror the corresponding Flutter types closely enough that the
+//! rendering docs scattered through this crate (which were written against
+//! Flutter's vocabulary) can be taken at face value.
+
+/// An immutable, unpositioned 2-D size.
+///
+/// A [Size] has no notion of where it sits in space; see `Offset` for that.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Size {
+    pub width: f32,
+    pub height: f32,
+}
+
+impl Default for Size {
+    fn default() -> Self {
+        Size::zero()
+    }
+}
+
+impl Size {
+    pub const fn new(width: f32, height: f32) -> Self {
+        Size { width, height }
+    }
+
+    /// A size with zero width and height.
+    pub const fn zero() -> Self {
+        Size::new(0.0, 0.0)
+    }
+
+    /// A size whose width and height are both unbounded.
+    pub const fn infinite() -> Self {
+        Size::new(f32::INFINITY, f32::INFINITY)
+    }
+}
+
+/// A point, or a translation, in a 2-D Cartesian coordinate system.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Offset {
+    pub dx: f32,
+    pub dy: f32,
+}
+
+impl Offset {
+    pub const fn new(dx: f32, dy: f32) -> Self {
+        Offset { dx, dy }
+    }
+
+    /// The origin: no translation at all.
+    pub const fn zero() -> Self {
+        Offset::new(0.0, 0.0)
+    }
+}
+
+impl std::ops::Add for Offset {
+    type Output = Offset;
+
+    fn add(self, rhs: Offset) -> Offset {
+        Offset::new(self.dx + rhs.dx, self.dy + rhs.dy)
+    }
+}
+
+impl std::ops::Sub for Offset {
+    type Output = Offset;
+
+    fn sub(self, rhs: Offset) -> Offset {
+        Offset::new(self.dx - rhs.dx, self.dy - rhs.dy)
+    }
+}
+
+/// An axis-aligned rectangle, given by its top-left corner and its size.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Rect {
+    pub left: f32,
+    pub top: f32,
+    pub width: f32,
+    pub height: f32,
+}
+
+impl Rect {
+    pub const fn new(left: f32, top: f32, width: f32, height: f32) -> Self {
+        Rect {
+            left,
+            top,
+            width,
+            height,
+        }
+    }
+
+    /// The rectangle of `size`, positioned at the origin.
+    pub const fn from_size(size: Size) -> Self {
+        Rect::new(0.0, 0.0, size.width, size.height)
+    }
+
+    /// Whether `point` lies within this rectangle. The top-left corner is
+    /// inclusive and the bottom-right corner is exclusive, matching
+    /// Flutter's `Rect.contains`.
+    pub fn contains(&self, point: Offset) -> bool {
+        point.dx >= self.left
+            && point.dx < self.left + self.width
+            && point.dy >= self.top
+            && point.dy < self.top + self.height
+    }
+}
+
+/// A 4x4 transform matrix, stored row-major as `rows[row][col]`.
+///
+/// Used to carry paint transforms (3D transforms, perspective, or any other
+/// arbitrary change of basis a layer applies) through hit testing; see
+/// `BoxHitTestResult::add_with_paint_transform`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Matrix4 {
+    rows: [[f32; 4]; 4],
+}
+
+impl Matrix4 {
+    pub const fn from_rows(rows: [[f32; 4]; 4]) -> Self {
+        Matrix4 { rows }
+    }
+
+    pub const fn identity() -> Self {
+        Matrix4::from_rows([
+            [1.0, 0.0, 0.0, 0.0],
+            [0.0, 1.0, 0.0, 0.0],
+            [0.0, 0.0, 1.0, 0.0],
+            [0.0, 0.0, 0.0, 1.0],
+        ])
+    }
+
+    /// A matrix that translates by `offset` in the x/y plane.
+    pub fn translation(offset: Offset) -> Self {
+        let mut m = Matrix4::identity();
+        m.rows[0][3] = offset.dx;
+        m.rows[1][3] = offset.dy;
+        m
+    }
+
+    /// Applies this matrix to `(point.dx, point.dy, 0, 1)` and returns the
+    /// resulting x/y after perspective division.
+    pub fn transform_point(&self, point: Offset) -> Offset {
+        let v = [point.dx, point.dy, 0.0, 1.0];
+        let mut out = [0.0f32; 4];
+        for (r, out_r) in out.iter_mut().enumerate() {
+            *out_r = (0..4).map(|c| self.rows[r][c] * v[c]).sum();
+        }
+        if out[3] != 0.0 && out[3] != 1.0 {
+            Offset::new(out[0] / out[3], out[1] / out[3])
+        } else {
+            Offset::new(out[0], out[1])
+        }
+    }
+
+    /// The inverse of this matrix, or `None` if it is singular (determinant
+    /// of zero), in which case it collapses space onto a lower dimension
+    /// and there is no well-defined way to map a position back through it.
+    pub fn inverse(&self) -> Option<Matrix4> {
+        fn det3(m: [[f32; 3]; 3]) -> f32 {
+            m[0][0] * (m[1][1] * m[2][2] - m[1][2] * m[2][1])
+                - m[0][1] * (m[1][0] * m[2][2] - m[1][2] * m[2][0])
+                + m[0][2] * (m[1][0] * m[2][1] - m[1][1] * m[2][0])
+        }
+
+        fn minor(rows: &[[f32; 4]; 4], skip_row: usize, skip_col: usize) -> [[f32; 3]; 3] {
+            let mut out = [[0.0f32; 3]; 3];
+            let mut oi = 0;
+            for (i, row) in rows.iter().enumerate() {
+                if i == skip_row {
+                    continue;
+                }
+                let mut oj = 0;
+                for (j, &value) in row.iter().enumerate() {
+                    if j == skip_col {
+                        continue;
+                    }
+                    out[oi][oj] = value;
+                    oj += 1;
+                }
+                oi += 1;
+            }
+            out
+        }
+
+        let mut cofactors = [[0.0f32; 4]; 4];
+        for (i, cofactor_row) in cofactors.iter_mut().enumerate() {
+            for (j, cofactor) in cofactor_row.iter_mut().enumerate() {
+                let sign = if (i + j) % 2 == 0 { 1.0 } else { -1.0 };
+                *cofactor = sign * det3(minor(&self.rows, i, j));
+            }
+        }
+
+        let determinant: f32 = (0..4).map(|j| self.rows[0][j] * cofactors[0][j]).sum();
+        if determinant.abs() < f32::EPSILON {
+            return None;
+        }
+
+        let mut inverse = [[0.0f32; 4]; 4];
+        for (i, row) in inverse.iter_mut().enumerate() {
+            for (j, cell) in row.iter_mut().enumerate() {
+                // The adjugate is the transpose of the cofactor matrix.
+                *cell = cofactors[j][i] / determinant;
+            }
+        }
+        Some(Matrix4::from_rows(inverse))
+    }
+}
+
+/// Insets from each of the four sides of a rectangle, e.g. padding or
+/// margins applied to a box.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct EdgeInsets {
+    pub left: f32,
+    pub top: f32,
+    pub right: f32,
+    pub bottom: f32,
+}
+
+impl EdgeInsets {
+    pub const fn zero() -> Self {
+        EdgeInsets::all(0.0)
+    }
+
+    /// The same inset on all four sides.
+    pub const fn all(value: f32) -> Self {
+        EdgeInsets {
+            left: value,
+            top: value,
+            right: value,
+            bottom: value,
+        }
+    }
+
+    /// `horizontal` on the left and right, `vertical` on the top and bottom.
+    pub const fn symmetric(horizontal: f32, vertical: f32) -> Self {
+        EdgeInsets {
+            left: horizontal,
+            top: vertical,
+            right: horizontal,
+            bottom: vertical,
+        }
+    }
+
+    /// The total amount of inset along the horizontal axis.
+    pub fn horizontal(&self) -> f32 {
+        self.left + self.right
+    }
+
+    /// The total amount of inset along the vertical axis.
+    pub fn vertical(&self) -> f32 {
+        self.top + self.bottom
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn identity_inverts_to_itself() {
+        let inverse = Matrix4::identity().inverse().unwrap();
+        assert_eq!(inverse, Matrix4::identity());
+    }
+
+    #[test]
+    fn translation_inverts_to_the_opposite_translation() {
+        let translation = Matrix4::translation(Offset::new(10.0, -5.0));
+        let inverse = translation.inverse().unwrap();
+        let point = Offset::new(3.0, 4.0);
+
+        let transformed = translation.transform_point(point);
+        assert_eq!(inverse.transform_point(transformed), point);
+    }
+
+    #[test]
+    fn singular_matrix_has_no_inverse() {
+        // A matrix with a zeroed-out row collapses space and cannot be
+        // inverted.
+        let singular = Matrix4::from_rows([
+            [0.0, 0.0, 0.0, 0.0],
+            [0.0, 1.0, 0.0, 0.0],
+            [0.0, 0.0, 1.0, 0.0],
+            [0.0, 0.0, 0.0, 1.0],
+        ]);
+        assert!(singular.inverse().is_none());
+    }
+
+    #[test]
+    fn rect_contains_is_half_open() {
+        let rect = Rect::new(0.0, 0.0, 10.0, 10.0);
+        assert!(rect.contains(Offset::new(0.0, 0.0)));
+        assert!(!rect.contains(Offset::new(10.0, 10.0)));
+        assert!(!rect.contains(Offset::new(-0.1, 5.0)));
+    }
+}
@@ -1,3 +1,9 @@
+pub mod constraints;
+pub mod geometry;
+pub mod rendering;
+
+pub use constraints::Constraints;
+
 #[cfg(test)]
 mod tests {
     use stretch::{geometry::Size, style::*};
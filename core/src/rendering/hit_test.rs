@@ -0,0 +1,168 @@
+use std::{cell::RefCell, rc::Rc};
+
+use super::RenderBox;
+use crate::geometry::{Matrix4, Offset};
+
+/// A pointer interaction delivered to whatever render object a hit test
+/// found, via [RenderBox::handle_event](super::RenderBox::handle_event) (or
+/// the [RenderObject](super::RenderObject) counterpart). Each variant's
+/// [Offset] is the pointer's position in the coordinate space of the render
+/// object being notified, mirroring how its [HitTestEntry::local_position]
+/// was recorded.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PointerEvent {
+    Down(Offset),
+    Move(Offset),
+    Up(Offset),
+    Cancel(Offset),
+}
+
+/// One render object that a hit test passed through, together with the
+/// position of the hit expressed in that object's own local coordinate
+/// space.
+#[derive(Debug, Clone)]
+pub struct HitTestEntry {
+    pub target: Rc<RefCell<dyn RenderBox>>,
+    pub local_position: Offset,
+}
+
+/// [HitTestEntry], named to match the box-specific helpers on
+/// [BoxHitTestResult] that produce it; box hit testing has no target kind
+/// other than [RenderBox], so the two are the same type.
+pub type BoxHitTestEntry = HitTestEntry;
+
+/// The ordered record of every render object a hit test passed through.
+///
+/// Entries are accumulated front-to-back, so the topmost render object that
+/// absorbed the hit (if any) is [path](HitTestResult::path)'s first entry.
+#[derive(Debug, Default)]
+pub struct HitTestResult {
+    path: Vec<HitTestEntry>,
+}
+
+impl HitTestResult {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends `entry` to the path.
+    pub fn add(&mut self, entry: HitTestEntry) {
+        self.path.push(entry);
+    }
+
+    /// The entries recorded so far, topmost first.
+    pub fn path(&self) -> &[HitTestEntry] {
+        &self.path
+    }
+}
+
+/// A [HitTestResult] specialized for box hit testing, adding the transform
+/// helpers a [RenderBox] parent uses when delegating a hit test to a child
+/// that doesn't share its exact coordinate space.
+#[derive(Debug, Default)]
+pub struct BoxHitTestResult {
+    inner: HitTestResult,
+}
+
+impl BoxHitTestResult {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends `entry` to the path.
+    pub fn add(&mut self, entry: HitTestEntry) {
+        self.inner.add(entry);
+    }
+
+    /// The entries recorded so far, topmost first.
+    pub fn path(&self) -> &[HitTestEntry] {
+        self.inner.path()
+    }
+
+    /// Hit-tests a child that paints at a simple offset from this box's
+    /// origin: `position` is translated into the child's local coordinate
+    /// space by subtracting `offset` before `hit_test` runs.
+    pub fn add_with_paint_offset(
+        &mut self,
+        offset: Offset,
+        position: Offset,
+        hit_test: impl FnOnce(&mut BoxHitTestResult, Offset) -> bool,
+    ) -> bool {
+        hit_test(self, position - offset)
+    }
+
+    /// Hit-tests a child reached through an arbitrary paint `transform`
+    /// (e.g. a 3D rotation or a perspective projection): `transform` is
+    /// inverted and used to map `position` into the child's local
+    /// coordinate space before `hit_test` runs. Returns `false` without
+    /// calling `hit_test` if `transform` is singular and so cannot be
+    /// inverted.
+    pub fn add_with_paint_transform(
+        &mut self,
+        transform: &Matrix4,
+        position: Offset,
+        hit_test: impl FnOnce(&mut BoxHitTestResult, Offset) -> bool,
+    ) -> bool {
+        match transform.inverse() {
+            Some(inverse) => self.add_with_raw_transform(&inverse, position, hit_test),
+            None => false,
+        }
+    }
+
+    /// Hit-tests a child using a caller-supplied `inverse_transform`
+    /// directly (already inverted, unlike
+    /// [add_with_paint_transform](BoxHitTestResult::add_with_paint_transform)),
+    /// mapping `position` into the child's local coordinate space before
+    /// `hit_test` runs.
+    pub fn add_with_raw_transform(
+        &mut self,
+        inverse_transform: &Matrix4,
+        position: Offset,
+        hit_test: impl FnOnce(&mut BoxHitTestResult, Offset) -> bool,
+    ) -> bool {
+        hit_test(self, inverse_transform.transform_point(position))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::rendering::render_box::RenderProxyBox;
+
+    fn entry_at(local_position: Offset) -> HitTestEntry {
+        HitTestEntry {
+            target: Rc::new(RefCell::new(RenderProxyBox::new())),
+            local_position,
+        }
+    }
+
+    #[test]
+    fn result_records_entries_in_add_order() {
+        let mut result = HitTestResult::new();
+        result.add(entry_at(Offset::new(1.0, 1.0)));
+        result.add(entry_at(Offset::new(2.0, 2.0)));
+
+        assert_eq!(result.path().len(), 2);
+        assert_eq!(result.path()[0].local_position, Offset::new(1.0, 1.0));
+        assert_eq!(result.path()[1].local_position, Offset::new(2.0, 2.0));
+    }
+
+    #[test]
+    fn add_with_paint_offset_translates_position_into_child_space() {
+        let mut result = BoxHitTestResult::new();
+        let mut seen = None;
+        result.add_with_paint_offset(Offset::new(10.0, 20.0), Offset::new(15.0, 25.0), |_, pos| {
+            seen = Some(pos);
+            true
+        });
+        assert_eq!(seen, Some(Offset::new(5.0, 5.0)));
+    }
+
+    #[test]
+    fn add_with_paint_transform_fails_closed_on_a_singular_transform() {
+        let singular = Matrix4::from_rows([[0.0; 4]; 4]);
+        let mut result = BoxHitTestResult::new();
+        let hit = result.add_with_paint_transform(&singular, Offset::zero(), |_, _| true);
+        assert!(!hit);
+    }
+}
@@ -0,0 +1,96 @@
+use std::collections::HashMap;
+
+/// Which of the four intrinsic-sizing queries a cached result is for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum IntrinsicDimension {
+    MinWidth,
+    MaxWidth,
+    MinHeight,
+    MaxHeight,
+}
+
+/// Per-node memoization for [RenderBox](super::RenderBox)'s intrinsic
+/// queries.
+///
+/// Intrinsic computations recurse over the subtree, so recomputing them on
+/// every call can blow up to `O(n^2)` (or worse) for a deep tree queried
+/// repeatedly during a single layout. Results are cached by the exact
+/// `(dimension, extent)` pair they were computed for; the whole cache
+/// should be dropped whenever this node's layout inputs change, since a
+/// stale entry is otherwise indistinguishable from a valid one.
+#[derive(Debug, Default)]
+pub struct IntrinsicCache {
+    entries: HashMap<(IntrinsicDimension, u32), f32>,
+}
+
+impl IntrinsicCache {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the cached result for `(dimension, extent)`, if any.
+    ///
+    /// `extent` is compared by its exact bit pattern (via `f32::to_bits`)
+    /// rather than a tolerance, matching how Flutter's `_cachedIntrinsicSizes`
+    /// key off the literal extent passed in; an infinite extent is a valid,
+    /// distinctly-cached key, meaning "preferred unconstrained size".
+    pub fn get(&self, dimension: IntrinsicDimension, extent: f32) -> Option<f32> {
+        self.entries.get(&(dimension, extent.to_bits())).copied()
+    }
+
+    /// Records `value` as the result for `(dimension, extent)`.
+    pub fn insert(&mut self, dimension: IntrinsicDimension, extent: f32, value: f32) {
+        self.entries.insert((dimension, extent.to_bits()), value);
+    }
+
+    /// Drops every cached result.
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn get_on_an_empty_cache_misses() {
+        let cache = IntrinsicCache::new();
+        assert_eq!(cache.get(IntrinsicDimension::MinWidth, 10.0), None);
+    }
+
+    #[test]
+    fn insert_then_get_round_trips() {
+        let mut cache = IntrinsicCache::new();
+        cache.insert(IntrinsicDimension::MaxHeight, 50.0, 123.0);
+        assert_eq!(cache.get(IntrinsicDimension::MaxHeight, 50.0), Some(123.0));
+    }
+
+    #[test]
+    fn entries_are_keyed_by_both_dimension_and_extent() {
+        let mut cache = IntrinsicCache::new();
+        cache.insert(IntrinsicDimension::MinWidth, 10.0, 1.0);
+        cache.insert(IntrinsicDimension::MaxWidth, 10.0, 2.0);
+        cache.insert(IntrinsicDimension::MinWidth, 20.0, 3.0);
+
+        assert_eq!(cache.get(IntrinsicDimension::MinWidth, 10.0), Some(1.0));
+        assert_eq!(cache.get(IntrinsicDimension::MaxWidth, 10.0), Some(2.0));
+        assert_eq!(cache.get(IntrinsicDimension::MinWidth, 20.0), Some(3.0));
+    }
+
+    #[test]
+    fn infinite_extent_is_its_own_distinct_key() {
+        let mut cache = IntrinsicCache::new();
+        cache.insert(IntrinsicDimension::MinWidth, f32::INFINITY, 99.0);
+        assert_eq!(cache.get(IntrinsicDimension::MinWidth, f32::INFINITY), Some(99.0));
+        assert_eq!(cache.get(IntrinsicDimension::MinWidth, 1000.0), None);
+    }
+
+    #[test]
+    fn clear_drops_every_entry() {
+        let mut cache = IntrinsicCache::new();
+        cache.insert(IntrinsicDimension::MinWidth, 10.0, 1.0);
+        cache.clear();
+        assert_eq!(cache.get(IntrinsicDimension::MinWidth, 10.0), None);
+    }
+}
@@ -1,11 +1,206 @@
-pub use self::render_box::{RenderBox, RenderBoxObject};
+pub use self::hit_test::{
+    BoxHitTestEntry, BoxHitTestResult, HitTestEntry, HitTestResult, PointerEvent,
+};
+pub use self::intrinsics::{IntrinsicCache, IntrinsicDimension};
+pub use self::parent_data::{BoxParentData, ContainerParentData, ParentData};
+pub use self::pipeline_owner::{Layoutable, PipelineOwner};
+pub use self::render_box::{
+    RenderBox, RenderBoxObject, RenderConstrainedBox, RenderOpacity, RenderProxyBox,
+};
+pub use self::render_flex::{FlexBasis, FlexParentData, RenderFlex};
+pub use self::render_sliver::RenderSliver;
 use crate::Constraints;
+use std::{
+    cell::RefCell,
+    fmt::Debug,
+    rc::{Rc, Weak},
+};
 
+mod hit_test;
+mod intrinsics;
+mod parent_data;
+mod pipeline_owner;
 mod render_box;
+mod render_flex;
+mod render_sliver;
 
-pub trait RenderObject {
+/// The mutable layout bookkeeping every [RenderObject] carries, regardless
+/// of which concrete render object it belongs to.
+///
+/// Concrete render objects embed one of these and expose it through
+/// [RenderObject::render_data]/[RenderObject::render_data_mut]; this is what
+/// lets `mark_needs_layout` and [PipelineOwner] be written once against the
+/// trait instead of once per render object.
+#[derive(Debug)]
+pub struct RenderObjectData<C: Constraints> {
+    needs_layout: bool,
+    parent_uses_size: bool,
+    last_constraints: Option<C>,
+    /// The nearest relayout boundary at or above this node, inclusive.
+    /// Recomputed by [RenderObject::layout] every time this node is laid
+    /// out; compared by identity in `mark_needs_layout` to decide whether
+    /// this node can register itself with the [PipelineOwner] directly or
+    /// must defer to its parent.
+    relayout_boundary: Option<Weak<RefCell<dyn Layoutable>>>,
+    parent: Option<Weak<RefCell<dyn Layoutable>>>,
+    owner: Option<Rc<RefCell<PipelineOwner>>>,
+    /// A handle to this node itself, so it can register with a
+    /// [PipelineOwner] or be recorded as another node's relayout boundary.
+    /// Populated by whoever constructs the owning `Rc<RefCell<Self>>`
+    /// (typically via `Rc::new_cyclic`).
+    self_weak: Option<Weak<RefCell<dyn Layoutable>>>,
+}
+
+impl<C: Constraints> Default for RenderObjectData<C> {
+    fn default() -> Self {
+        RenderObjectData {
+            needs_layout: false,
+            parent_uses_size: false,
+            last_constraints: None,
+            relayout_boundary: None,
+            parent: None,
+            owner: None,
+            self_weak: None,
+        }
+    }
+}
+
+impl<C: Constraints> RenderObjectData<C> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Records the handle used to refer to this node from elsewhere in the
+    /// tree (its own relayout boundary, a parent pointer, a pipeline
+    /// owner's dirty list). Call this once, right after constructing the
+    /// `Rc<RefCell<Self>>` that owns this data, e.g. from within
+    /// `Rc::new_cyclic`.
+    pub fn set_self_weak(&mut self, self_weak: Weak<RefCell<dyn Layoutable>>) {
+        self.self_weak = Some(self_weak);
+    }
+
+    /// Attaches this node to `parent`, to be notified via
+    /// `mark_parent_needs_layout` when this node is dirtied but is not
+    /// itself a relayout boundary.
+    pub fn set_parent(&mut self, parent: Weak<RefCell<dyn Layoutable>>) {
+        self.parent = Some(parent);
+    }
+
+    /// Assigns the [PipelineOwner] this node (and, transitively, its
+    /// subtree) belongs to.
+    pub fn set_owner(&mut self, owner: Rc<RefCell<PipelineOwner>>) {
+        self.owner = Some(owner);
+    }
+
+    /// Detaches this node from its parent, the inverse of [set_parent].
+    pub fn clear_parent(&mut self) {
+        self.parent = None;
+    }
+}
+
+pub trait RenderObject: Debug {
     type Constraints: Constraints;
 
+    /// The type of data this render object's *parent* stashes on it, e.g. a
+    /// child offset for a box parent, or flex/sibling bookkeeping for a
+    /// multi-child one. Populated via
+    /// [setup_parent_data](RenderObject::setup_parent_data) when the parent
+    /// [adopt_child](RenderObject::adopt_child)s this node.
+    type ParentData: ParentData;
+
+    /// Access to this render object's shared layout bookkeeping. See
+    /// [RenderObjectData] for what it stores and why it is factored out of
+    /// the trait itself.
+    fn render_data(&self) -> &RenderObjectData<Self::Constraints>;
+
+    /// Mutable access to this render object's shared layout bookkeeping.
+    fn render_data_mut(&mut self) -> &mut RenderObjectData<Self::Constraints>;
+
+    /// The data this node's parent has stashed on it, if any parent has
+    /// adopted it yet.
+    fn parent_data(&self) -> Option<&Self::ParentData>;
+
+    /// Mutable access to the data this node's parent has stashed on it.
+    fn parent_data_mut(&mut self) -> Option<&mut Self::ParentData>;
+
+    /// Replaces this node's parent data wholesale. Called by
+    /// [setup_parent_data](RenderObject::setup_parent_data) and
+    /// [drop_child](RenderObject::drop_child); not normally called directly.
+    fn set_parent_data(&mut self, data: Option<Self::ParentData>);
+
+    /// Override this on a parent to attach its own flavor of parent data to
+    /// a newly adopted `child`, e.g. a parent that lays children out in a
+    /// flex row would attach parent data carrying a flex factor alongside
+    /// the usual offset. The default attaches a default-constructed
+    /// instance of whatever [ParentData] type `child` itself declares,
+    /// which is correct for parents with no extra per-child state to track.
+    fn setup_parent_data<T>(&self, child: &mut T)
+    where
+        T: RenderObject,
+        T::ParentData: Default,
+    {
+        child.set_parent_data(Some(T::ParentData::default()));
+    }
+
+    /// Called when this render object acquires `child` as one of its
+    /// children: attaches parent data (via
+    /// [setup_parent_data](RenderObject::setup_parent_data)) if `child`
+    /// doesn't already have any, records `self` as its parent, inherits
+    /// this node's [PipelineOwner], and marks *this node* (not `child`) as
+    /// needing layout, since acquiring a new child is what changes this
+    /// node's own layout inputs; `child` itself gets laid out when this
+    /// node's `perform_layout` reaches it. Marking `child` directly instead
+    /// would also re-enter `self`'s own `RefCell` borrow, since `child` has
+    /// no relayout boundary of its own yet and so defers straight back up
+    /// to `self`.
+    ///
+    /// Being generic over a concrete `T: RenderObject`, this is only
+    /// callable by a multi-child render object written directly against
+    /// `RenderObject`; see [ContainerParentData](super::ContainerParentData)
+    /// for why the crate's existing box-world multi-child types (e.g.
+    /// [RenderFlex](super::RenderFlex)), which hold children as `dyn
+    /// RenderBox` trait objects instead, don't go through it.
+    fn adopt_child<T>(&mut self, child: &Rc<RefCell<T>>)
+    where
+        T: RenderObject + 'static,
+        T::ParentData: Default,
+    {
+        if child.borrow().parent_data().is_none() {
+            self.setup_parent_data(&mut *child.borrow_mut());
+        }
+
+        let parent = self.render_data().self_weak.clone().expect(
+            "a render object must be constructed behind Rc::new_cyclic before adopting children",
+        );
+        let owner = self.render_data().owner.clone();
+
+        {
+            let mut child_ref = child.borrow_mut();
+            child_ref.render_data_mut().set_parent(parent);
+            if let Some(owner) = owner {
+                child_ref.render_data_mut().set_owner(owner);
+            }
+        }
+
+        self.mark_needs_layout();
+    }
+
+    /// Called when this render object releases `child`, the inverse of
+    /// [adopt_child](RenderObject::adopt_child): lets the child's parent
+    /// data detach (e.g. clearing sibling links), then clears its parent
+    /// data and parent pointer.
+    fn drop_child<T>(&mut self, child: &Rc<RefCell<T>>)
+    where
+        T: RenderObject + 'static,
+    {
+        let mut child_ref = child.borrow_mut();
+        if let Some(data) = child_ref.parent_data_mut() {
+            data.detach();
+        }
+        child_ref.set_parent_data(None);
+        child_ref.render_data_mut().clear_parent();
+    }
+
     /// Mark this render object's layout information as dirty, and either
     /// register this object with its [PipelineOwner], or defer to the
     /// parent, depending on whether this object is a relayout boundary or
@@ -28,26 +223,52 @@ pub trait RenderObject {
     /// as needing layout. In that case, since both the parent and the child
     /// need to have their layout recomputed, the pipeline owner is only
     /// notified about the parent; when the parent is laid out, it will call
-    /// the child's [layout] method and thus the child will be laid out as
-    /// well.
-    ///
-    /// Once [markNeedsLayout] has been called on a render object,
-    /// [debugNeedsLayout] returns true for that render object until just after
-    /// the pipeline owner has called [layout] on the render object.
-    ///
-    /// ## Special cases
-    ///
-    /// Some subclasses of [RenderObject], notably [RenderBox], have other
-    /// situations in which the parent needs to be notified if the child is
-    /// dirtied (e.g., if the child's intrinsic dimensions or baseline changes).
-    /// Such subclasses override markNeedsLayout and either call
-    /// `super.markNeedsLayout()`, in the normal case, or call
-    /// [markParentNeedsLayout], in the case where the parent needs to be laid
-    /// out as well as the child.
-    ///
-    /// If [sizedByParent] has changed, calls
-    /// [markNeedsLayoutForSizedByParentChange] instead of [markNeedsLayout].
-    fn mark_needs_layout(&mut self);
+    /// the child's [layout](RenderObject::layout) method and thus the child
+    /// will be laid out as well.
+    fn mark_needs_layout(&mut self) {
+        if self.render_data().needs_layout {
+            return;
+        }
+        self.render_data_mut().needs_layout = true;
+
+        let self_handle = self
+            .render_data()
+            .self_weak
+            .as_ref()
+            .and_then(Weak::upgrade);
+        let boundary_handle = self
+            .render_data()
+            .relayout_boundary
+            .as_ref()
+            .and_then(Weak::upgrade);
+        // A node with no recorded boundary yet (e.g. before its first
+        // layout pass) defers to its parent, same as a node that knows it
+        // isn't its own boundary: only a node with no parent at all (the
+        // root) can safely treat itself as the boundary before its first
+        // layout pass, since there is nothing above it to defer to.
+        let is_own_boundary = match (&self_handle, &boundary_handle) {
+            (Some(this), Some(boundary)) => Rc::ptr_eq(this, boundary),
+            _ => self.render_data().parent.is_none(),
+        };
+
+        if is_own_boundary {
+            if let (Some(owner), Some(handle)) = (self.render_data().owner.clone(), self_handle) {
+                owner.borrow_mut().request_visual_update(handle);
+            }
+        } else {
+            self.mark_parent_needs_layout();
+        }
+    }
+
+    /// Marks this render object as needing layout, then does the same to
+    /// its parent, since this node does not have a relayout boundary of its
+    /// own and so can't resolve its own dirtiness in isolation.
+    fn mark_parent_needs_layout(&mut self) {
+        self.render_data_mut().needs_layout = true;
+        if let Some(parent) = self.render_data().parent.as_ref().and_then(Weak::upgrade) {
+            parent.borrow_mut().mark_needs_layout();
+        }
+    }
 
     /// Whether the constraints are the only input to the sizing algorithm (in
     /// particular, child nodes have no impact).
@@ -55,35 +276,55 @@ pub trait RenderObject {
     /// Returning false is always correct, but returning true can be more
     /// efficient when computing the size of this render object because we don't
     /// need to recompute the size if the constraints don't change.
-    ///
-    /// Typically, subclasses will always return the same value. If the value
-    /// can change, then, when it does change, the subclass should make sure
-    /// to call [markNeedsLayoutForSizedByParentChange].
     fn sized_by_paren(&self) -> bool {
-        sizedByParent
+        false
     }
 
     /// Do the work of computing the layout for this render object.
     ///
-    /// Do not call this function directly: call [layout] instead. This function
-    /// is called by [layout] when there is actually work to be done by this
-    /// render object during layout. The layout constraints provided by your
-    /// parent are available via the [constraints] getter.
-    ///
-    /// If [sizedByParent] is true, then this function should not actually
-    /// change the dimensions of this render object. Instead, that work
-    /// should be done by [performResize]. If [sizedByParent] is false, then
-    /// this function should both change the dimensions of this render
-    /// object and instruct its children to layout.
-    ///
-    /// In implementing this function, you must call [layout] on each of your
-    /// children, passing true for parentUsesSize if your layout information is
-    /// dependent on your child's layout information. Passing true for
-    /// parentUsesSize ensures that this render object will undergo layout if
-    /// the child undergoes layout. Otherwise, the child can change its
-    /// layout information without informing this render object.
+    /// Do not call this function directly: call [layout](RenderObject::layout)
+    /// instead. This function is called by [layout](RenderObject::layout)
+    /// when there is actually work to be done by this render object during
+    /// layout. The layout constraints provided by your parent are available
+    /// via the `constraints` argument.
     fn perform_layout(&self, constraints: &Self::Constraints);
 
+    /// Computes the layout for this render object given `constraints` from
+    /// its parent.
+    ///
+    /// Pass `parent_uses_size = true` if the parent's own layout depends on
+    /// this render object's resulting size; this is what determines whether
+    /// this node can be its own relayout boundary. A node is a relayout
+    /// boundary when it has no parent (it is the root), its constraints are
+    /// tight, `parent_uses_size` is false, or
+    /// [sized_by_paren](RenderObject::sized_by_paren) returns true.
+    fn layout(&mut self, constraints: Self::Constraints, parent_uses_size: bool) {
+        let is_boundary = self.render_data().parent.is_none()
+            || constraints.is_tight()
+            || !parent_uses_size
+            || self.sized_by_paren();
+
+        self.perform_layout(&constraints);
+
+        let self_weak = self.render_data().self_weak.clone();
+        let parent_boundary = self.render_data().relayout_boundary.clone();
+        let data = self.render_data_mut();
+        data.last_constraints = Some(constraints);
+        data.parent_uses_size = parent_uses_size;
+        data.needs_layout = false;
+        data.relayout_boundary = if is_boundary { self_weak } else { parent_boundary };
+    }
+
+    /// Re-runs layout using the constraints this node was last given.
+    /// Called by [PipelineOwner::flush_layout] on every node it drains from
+    /// its dirty list.
+    fn relayout(&mut self) {
+        if let Some(constraints) = self.render_data().last_constraints.clone() {
+            self.perform_layout(&constraints);
+        }
+        self.render_data_mut().needs_layout = false;
+    }
+
     /// Whether this render object repaints separately from its parent.
     ///
     /// Override this in subclasses to indicate that instances of your class
@@ -105,5 +346,107 @@ pub trait RenderObject {
 
     /// Override this method to handle pointer events that hit this render
     /// object.
-    fn handle_event(&mut self, event: PointEvent, entry: Box<HitTestEntry>) {}
+    fn handle_event(&mut self, event: PointerEvent, entry: Box<HitTestEntry>) {
+        let _ = (event, entry);
+    }
+}
+
+impl<T> Layoutable for T
+where
+    T: RenderObject + 'static,
+{
+    fn needs_layout(&self) -> bool {
+        self.render_data().needs_layout
+    }
+
+    fn mark_needs_layout(&mut self) {
+        RenderObject::mark_needs_layout(self)
+    }
+
+    fn relayout(&mut self) {
+        RenderObject::relayout(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constraints::BoxConstraints;
+    use crate::geometry::Size;
+    use crate::rendering::render_box::RenderProxyBox;
+
+    /// A function (rather than an inline `as` cast) so the unsized
+    /// coercion from a concrete `Weak<RefCell<T>>` to
+    /// `Weak<RefCell<dyn Layoutable>>` happens at a clean type boundary;
+    /// doing it inline inside `Rc::new_cyclic`'s closure confuses type
+    /// inference into unifying the closure's output type with the trait
+    /// object instead of the concrete `T`.
+    fn as_layoutable_weak<T: Layoutable + 'static>(weak: &Weak<RefCell<T>>) -> Weak<RefCell<dyn Layoutable>> {
+        weak.clone()
+    }
+
+    /// Builds a `RenderBoxObject` wired up with a working `self_weak`, the
+    /// way real construction (via `Rc::new_cyclic`) would, so it can
+    /// register itself with a `PipelineOwner` or be recorded as another
+    /// node's relayout boundary.
+    fn new_box_node<R: RenderBox + 'static>(inner: R) -> Rc<RefCell<RenderBoxObject<R>>> {
+        Rc::new_cyclic(|weak| {
+            let mut object = RenderBoxObject::new(inner);
+            object
+                .render_data_mut()
+                .set_self_weak(as_layoutable_weak(weak));
+            RefCell::new(object)
+        })
+    }
+
+    #[test]
+    fn mark_needs_layout_defers_to_the_parent_when_no_boundary_is_recorded_yet() {
+        let owner = Rc::new(RefCell::new(PipelineOwner::new()));
+
+        let parent = new_box_node(RenderProxyBox::new());
+        parent.borrow_mut().render_data_mut().set_owner(Rc::clone(&owner));
+        // Give the parent a relayout boundary of its own (it has no parent,
+        // so it becomes its own boundary) before the child is attached.
+        RenderObject::layout(
+            &mut *parent.borrow_mut(),
+            BoxConstraints::tight(Size::new(100.0, 100.0)),
+            false,
+        );
+
+        // A freshly constructed child has no `relayout_boundary` of its own
+        // yet, so marking it dirty must defer to the parent rather than
+        // registering the child with the owner directly: the child cannot
+        // resolve its own dirtiness in isolation without a parent to lay it
+        // out.
+        let child = new_box_node(RenderProxyBox::new());
+        child
+            .borrow_mut()
+            .render_data_mut()
+            .set_parent(as_layoutable_weak(&Rc::downgrade(&parent)));
+        RenderObject::mark_needs_layout(&mut *child.borrow_mut());
+        assert!(parent.borrow().needs_layout());
+
+        owner.borrow_mut().flush_layout();
+        assert!(!parent.borrow().needs_layout());
+
+        // The parent (the deferred-to boundary) was the one actually
+        // registered and relaid-out; the child's own dirty flag must
+        // survive that flush rather than being silently cleared without
+        // ever having been laid out itself.
+        assert!(child.borrow().needs_layout());
+    }
+
+    #[test]
+    fn adopt_child_marks_the_parent_dirty_without_reentering_its_own_borrow() {
+        let parent = new_box_node(RenderProxyBox::new());
+        let child = new_box_node(RenderProxyBox::new());
+
+        // Regression test: adopt_child used to mark the *child* dirty,
+        // which (since the child defers to its parent when it has no
+        // relayout boundary of its own) re-entered the parent's still-held
+        // `RefCell` borrow and panicked.
+        parent.borrow_mut().adopt_child(&child);
+
+        assert!(parent.borrow().needs_layout());
+    }
 }
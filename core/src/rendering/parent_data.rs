@@ -0,0 +1,159 @@
+use std::{
+    cell::RefCell,
+    fmt::Debug,
+    rc::{Rc, Weak},
+};
+
+use super::RenderObject;
+use crate::geometry::Offset;
+
+/// Base trait for data a parent render object stashes on one of its
+/// children, e.g. the child's offset or its position in a sibling list.
+///
+/// A concrete [RenderObject] declares the `ParentData` type it expects its
+/// own parent to attach via its `RenderObject::ParentData` associated type;
+/// the parent then attaches an instance of that type from
+/// [RenderObject::setup_parent_data] when it adopts the child.
+pub trait ParentData: Debug {
+    /// Called when the parent relinquishes this child, to let the parent
+    /// data release any state specific to being attached to a parent (e.g.
+    /// sibling links held by [ContainerParentData]).
+    fn detach(&mut self) {}
+}
+
+impl ParentData for () {}
+
+/// Parent data for render boxes whose parent positions them by a simple
+/// offset, which covers most box parents.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct BoxParentData {
+    /// The offset at which to paint the child, relative to the parent's
+    /// own origin.
+    pub offset: Offset,
+}
+
+impl ParentData for BoxParentData {}
+
+/// Parent data mixin for multi-child render objects that maintain their
+/// children as an intrusive doubly-linked list, rather than (or in addition
+/// to) a separate child `Vec`.
+///
+/// This is meant to be composed into a parent-specific parent data type
+/// alongside whatever else that parent needs to track per child, the way
+/// Flutter mixes `ContainerParentDataMixin` into richer parent data
+/// classes. It composes with [RenderObject::adopt_child]/`drop_child`,
+/// which are generic over a concrete `T: RenderObject` and so are only
+/// reachable by a multi-child render object written directly against
+/// `RenderObject` itself. [RenderFlex](super::RenderFlex), and every other
+/// multi-child box so far, instead holds its children as
+/// `Rc<RefCell<dyn RenderBox>>` trait objects for object safety — `dyn
+/// RenderBox` has no room for `RenderObject`'s associated `ParentData`
+/// type, so those boxes track their own ad hoc parent data (e.g.
+/// [FlexParentData](super::FlexParentData)) inline instead of through this
+/// mixin.
+#[derive(Debug)]
+pub struct ContainerParentData<T: RenderObject> {
+    /// The sibling painted immediately before this child, held weakly:
+    /// the forward `next_sibling` chain from the parent's first child is
+    /// what keeps the list's nodes alive, so a strong backward pointer here
+    /// would make every non-trivial list a reference cycle `Rc` can never
+    /// collect.
+    pub previous_sibling: Option<Weak<RefCell<T>>>,
+    pub next_sibling: Option<Rc<RefCell<T>>>,
+}
+
+impl<T: RenderObject> Default for ContainerParentData<T> {
+    fn default() -> Self {
+        ContainerParentData {
+            previous_sibling: None,
+            next_sibling: None,
+        }
+    }
+}
+
+impl<T: RenderObject + Debug> ParentData for ContainerParentData<T> {
+    fn detach(&mut self) {
+        self.previous_sibling = None;
+        self.next_sibling = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constraints::BoxConstraints;
+    use crate::rendering::RenderObjectData;
+
+    #[derive(Debug, Default)]
+    struct FakeBox {
+        render_data: RenderObjectData<BoxConstraints>,
+        parent_data: Option<BoxParentData>,
+    }
+
+    impl RenderObject for FakeBox {
+        type Constraints = BoxConstraints;
+        type ParentData = BoxParentData;
+
+        fn render_data(&self) -> &RenderObjectData<Self::Constraints> {
+            &self.render_data
+        }
+
+        fn render_data_mut(&mut self) -> &mut RenderObjectData<Self::Constraints> {
+            &mut self.render_data
+        }
+
+        fn parent_data(&self) -> Option<&Self::ParentData> {
+            self.parent_data.as_ref()
+        }
+
+        fn parent_data_mut(&mut self) -> Option<&mut Self::ParentData> {
+            self.parent_data.as_mut()
+        }
+
+        fn set_parent_data(&mut self, data: Option<Self::ParentData>) {
+            self.parent_data = data;
+        }
+
+        fn perform_layout(&self, _constraints: &BoxConstraints) {}
+    }
+
+    #[test]
+    fn default_has_no_siblings() {
+        let data = ContainerParentData::<FakeBox>::default();
+        assert!(data.previous_sibling.is_none());
+        assert!(data.next_sibling.is_none());
+    }
+
+    #[test]
+    fn previous_sibling_does_not_keep_its_target_alive() {
+        // The whole point of `previous_sibling` being a `Weak` is that a
+        // back-link alone must not keep a node alive.
+        let sibling = Rc::new(RefCell::new(FakeBox::default()));
+        let weak = Rc::downgrade(&sibling);
+
+        let data = ContainerParentData::<FakeBox> {
+            previous_sibling: Some(weak),
+            next_sibling: None,
+        };
+        assert!(data.previous_sibling.as_ref().unwrap().upgrade().is_some());
+
+        drop(sibling);
+        assert!(data.previous_sibling.as_ref().unwrap().upgrade().is_none());
+    }
+
+    #[test]
+    fn detach_clears_both_sibling_links() {
+        let next = Rc::new(RefCell::new(FakeBox::default()));
+        let previous = Rc::new(RefCell::new(FakeBox::default()));
+
+        let mut data = ContainerParentData::<FakeBox> {
+            next_sibling: Some(next),
+            previous_sibling: Some(Rc::downgrade(&previous)),
+        };
+
+        data.detach();
+
+        assert!(data.next_sibling.is_none());
+        assert!(data.previous_sibling.is_none());
+    }
+}
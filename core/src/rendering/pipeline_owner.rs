@@ -0,0 +1,149 @@
+use std::{cell::RefCell, fmt::Debug, rc::Rc};
+
+/// An object-safe view of a render object, used purely to batch and drain
+/// layout work.
+///
+/// [RenderObject](super::RenderObject) is generic over its
+/// [Constraints](crate::Constraints) type, so a [PipelineOwner] cannot hold
+/// a homogeneous list of render objects directly; it holds nodes through
+/// this narrower trait instead. A blanket impl provides it for every
+/// [RenderObject](super::RenderObject), so concrete render objects never
+/// need to implement it by hand.
+pub trait Layoutable: Debug {
+    /// Whether this node has been marked dirty and has not yet been
+    /// revisited by [PipelineOwner::flush_layout].
+    fn needs_layout(&self) -> bool;
+
+    /// Marks this node (and, if necessary, its ancestors up to the nearest
+    /// relayout boundary) as needing layout.
+    fn mark_needs_layout(&mut self);
+
+    /// Re-runs layout for this node using the constraints it was last given,
+    /// and clears its dirty flag.
+    fn relayout(&mut self);
+}
+
+/// Owns the set of render objects that are due for a layout pass.
+///
+/// Rather than eagerly recomputing layout whenever a render object is
+/// mutated, `mark_needs_layout` marks the node and its ancestors up to the
+/// nearest relayout boundary as dirty and registers only that boundary
+/// here; [flush_layout](PipelineOwner::flush_layout)
+/// is what actually walks the dirty set and brings every registered node's
+/// layout back up to date. This batches and coalesces what would otherwise
+/// be redundant layout work triggered by several sequential writes.
+#[derive(Debug, Default)]
+pub struct PipelineOwner {
+    nodes_needing_layout: Vec<Rc<RefCell<dyn Layoutable>>>,
+}
+
+impl PipelineOwner {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Registers `node` to be revisited by the next
+    /// [flush_layout](PipelineOwner::flush_layout) call.
+    ///
+    /// Called by a render object's `mark_needs_layout` once it reaches a
+    /// relayout boundary; not normally called directly.
+    pub fn request_visual_update(&mut self, node: Rc<RefCell<dyn Layoutable>>) {
+        self.nodes_needing_layout.push(node);
+    }
+
+    /// Updates every render object registered since the last call.
+    ///
+    /// Nodes are visited in registration order. A node whose `needs_layout`
+    /// flag was already cleared by an earlier node's layout (because laying
+    /// out an ancestor also lays out its dirty descendants) is skipped
+    /// rather than laid out twice.
+    pub fn flush_layout(&mut self) {
+        let dirty = std::mem::take(&mut self.nodes_needing_layout);
+        for node in dirty {
+            let mut node = node.borrow_mut();
+            if !node.needs_layout() {
+                continue;
+            }
+            node.relayout();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, Default)]
+    struct FakeNode {
+        dirty: bool,
+        relayout_count: u32,
+    }
+
+    impl Layoutable for FakeNode {
+        fn needs_layout(&self) -> bool {
+            self.dirty
+        }
+
+        fn mark_needs_layout(&mut self) {
+            self.dirty = true;
+        }
+
+        fn relayout(&mut self) {
+            self.relayout_count += 1;
+            self.dirty = false;
+        }
+    }
+
+    #[test]
+    fn flush_layout_relays_out_every_registered_node() {
+        let a = Rc::new(RefCell::new(FakeNode {
+            dirty: true,
+            relayout_count: 0,
+        }));
+        let b = Rc::new(RefCell::new(FakeNode {
+            dirty: true,
+            relayout_count: 0,
+        }));
+
+        let mut owner = PipelineOwner::new();
+        owner.request_visual_update(a.clone());
+        owner.request_visual_update(b.clone());
+        owner.flush_layout();
+
+        assert_eq!(a.borrow().relayout_count, 1);
+        assert_eq!(b.borrow().relayout_count, 1);
+        assert!(!a.borrow().needs_layout());
+    }
+
+    #[test]
+    fn flush_layout_skips_nodes_already_cleaned_by_an_earlier_node() {
+        // Mirrors a parent laying out a dirty child itself before the
+        // pipeline owner gets to the child's own registration.
+        let child = Rc::new(RefCell::new(FakeNode {
+            dirty: false,
+            relayout_count: 0,
+        }));
+
+        let mut owner = PipelineOwner::new();
+        owner.request_visual_update(child.clone());
+        owner.flush_layout();
+
+        assert_eq!(child.borrow().relayout_count, 0);
+    }
+
+    #[test]
+    fn flush_layout_drains_the_dirty_list() {
+        let node = Rc::new(RefCell::new(FakeNode {
+            dirty: true,
+            relayout_count: 0,
+        }));
+
+        let mut owner = PipelineOwner::new();
+        owner.request_visual_update(node.clone());
+        owner.flush_layout();
+        owner.flush_layout();
+
+        // The second flush has nothing registered, so no extra relayout.
+        assert_eq!(node.borrow().relayout_count, 1);
+    }
+}
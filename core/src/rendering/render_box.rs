@@ -1,8 +1,41 @@
-use super::RenderObject;
+use std::{
+    cell::{Ref, RefCell, RefMut},
+    fmt::Debug,
+    rc::Rc,
+};
+
+use super::{
+    BoxHitTestEntry, BoxHitTestResult, BoxParentData, IntrinsicCache, IntrinsicDimension,
+    PointerEvent, RenderObject, RenderObjectData,
+};
 use crate::constraints::BoxConstraints;
-use stretch::geometry::Size;
+use crate::geometry::{Offset, Rect, Size};
+
+pub trait RenderBox: Debug {
+    /// This box's size, as computed by the most recent layout pass.
+    fn size(&self) -> Size;
+
+    /// Records the size this box has chosen for itself. Called by
+    /// [perform_layout](RenderBox::perform_layout) implementations; not
+    /// normally called directly.
+    fn set_size(&mut self, size: Size);
+
+    /// Computes layout for this box given `constraints` from its parent.
+    /// `parent_uses_size` says whether the caller's own layout depends on
+    /// this box's resulting size. The default forwards straight to
+    /// [perform_layout](RenderBox::perform_layout), ignoring
+    /// `parent_uses_size`; override this instead of `perform_layout` if you
+    /// need to react to it directly.
+    fn layout(&mut self, constraints: &BoxConstraints, parent_uses_size: bool) {
+        let _ = parent_uses_size;
+        self.perform_layout(constraints);
+    }
+
+    /// Do the work of computing this box's layout: lay out any children and
+    /// call [set_size](RenderBox::set_size) with the result. Do not call
+    /// this directly; call [layout](RenderBox::layout) instead.
+    fn perform_layout(&mut self, constraints: &BoxConstraints);
 
-pub trait RenderBox {
     /// Determines the set of render objects located at the given position.
     ///
     /// Returns true, and adds any render objects that contain the point to the
@@ -29,13 +62,26 @@ pub trait RenderBox {
     /// be a child of a [RenderOpacity] object, which calls [hit_test] on
     /// its children when its opacity is zero even through it does not
     /// [paint] its children.
-    fn hit_test(&mut self, result: BoxHitTestResult, pos: Offset) {
-        if _size.contains(position) {
-            if hitTestChildren(result, pos) || hitTestSelf(position) {
-                result.add(BoxHitTestEntry(this, position));
-                return true;
-            }
+    ///
+    /// `self_handle` must point to the same box as `self`; it is threaded
+    /// through explicitly (rather than recovered from `self`) so that a
+    /// recorded [BoxHitTestEntry] can hold a strong reference to its target.
+    fn hit_test(
+        &mut self,
+        self_handle: &Rc<RefCell<dyn RenderBox>>,
+        result: &mut BoxHitTestResult,
+        position: Offset,
+    ) -> bool {
+        if Rect::from_size(self.size()).contains(position)
+            && (self.hit_test_children(result, position) || self.hit_test_self(position))
+        {
+            result.add(BoxHitTestEntry {
+                target: Rc::clone(self_handle),
+                local_position: position,
+            });
+            return true;
         }
+        false
     }
 
     /// Override this method if this render object can be hit even if its
@@ -49,6 +95,7 @@ pub trait RenderBox {
     /// Used by [hitTest]. If you override [hitTest] and do not call this
     /// function, then you don't need to implement this function.
     fn hit_test_self(&mut self, pos: Offset) -> bool {
+        let _ = pos;
         false
     }
 
@@ -64,19 +111,141 @@ pub trait RenderBox {
     /// This [RenderBox] is responsible for checking whether the given position
     /// is within its bounds.
     ///
-    /// If transforming is necessary, [HitTestResult.addWithPaintTransform],
-    /// [HitTestResult.addWithPaintOffset], or
-    /// [HitTestResult.addWithRawTransform] need to be invoked by the caller
-    /// to record the required transform operations in the [HitTestResult].
-    /// These methods will also help with applying the transform to
-    /// `position`.
+    /// If transforming is necessary, [BoxHitTestResult::add_with_paint_transform],
+    /// [BoxHitTestResult::add_with_paint_offset], or
+    /// [BoxHitTestResult::add_with_raw_transform] can be invoked by the
+    /// caller to map `position` into the child's coordinate space before
+    /// hit-testing it. These methods do not record the transform in the
+    /// [BoxHitTestResult]; only the entries that hit tests add are kept.
     ///
-    /// Used by [hitTest]. If you override [hitTest] and do not call this
-    /// function, then you don't need to implement this function.
-    fn hit_test_children(&mut self, pos: Offset) -> bool {
+    /// Used by [hit_test](RenderBox::hit_test). If you override
+    /// [hit_test](RenderBox::hit_test) and do not call this function, then
+    /// you don't need to implement this function.
+    fn hit_test_children(&mut self, result: &mut BoxHitTestResult, pos: Offset) -> bool {
+        let _ = (result, pos);
         false
     }
 
+    /// This box's intrinsic-dimension cache, keyed by which query was asked
+    /// and the cross-axis extent it was asked for. Implementors just need
+    /// to store one [IntrinsicCache] and hand back a reference to it; the
+    /// `get_*_intrinsic_*` wrappers below do the memoizing.
+    fn intrinsics_cache(&mut self) -> &mut IntrinsicCache;
+
+    /// The minimum width this box could be, given that it will be exactly
+    /// `height` tall, ignoring whether that would leave its content
+    /// overflowing.
+    ///
+    /// Do not call this directly; call
+    /// [get_min_intrinsic_width](RenderBox::get_min_intrinsic_width)
+    /// instead, which memoizes the result.
+    fn compute_min_intrinsic_width(&mut self, height: f32) -> f32 {
+        let _ = height;
+        0.0
+    }
+
+    /// The width beyond which increasing `height` no longer decreases how
+    /// wide this box needs to be, i.e. its preferred width at that height.
+    ///
+    /// Do not call this directly; call
+    /// [get_max_intrinsic_width](RenderBox::get_max_intrinsic_width)
+    /// instead, which memoizes the result.
+    fn compute_max_intrinsic_width(&mut self, height: f32) -> f32 {
+        let _ = height;
+        0.0
+    }
+
+    /// The minimum height this box could be, given that it will be exactly
+    /// `width` wide, ignoring whether that would leave its content
+    /// overflowing.
+    ///
+    /// Do not call this directly; call
+    /// [get_min_intrinsic_height](RenderBox::get_min_intrinsic_height)
+    /// instead, which memoizes the result.
+    fn compute_min_intrinsic_height(&mut self, width: f32) -> f32 {
+        let _ = width;
+        0.0
+    }
+
+    /// The height beyond which increasing `width` no longer decreases how
+    /// tall this box needs to be, i.e. its preferred height at that width.
+    ///
+    /// Do not call this directly; call
+    /// [get_max_intrinsic_height](RenderBox::get_max_intrinsic_height)
+    /// instead, which memoizes the result.
+    fn compute_max_intrinsic_height(&mut self, width: f32) -> f32 {
+        let _ = width;
+        0.0
+    }
+
+    /// The memoized [compute_min_intrinsic_width](RenderBox::compute_min_intrinsic_width).
+    /// Pass [f32::INFINITY] for `height` to ask for the preferred width
+    /// with no height constraint at all.
+    fn get_min_intrinsic_width(&mut self, height: f32) -> f32 {
+        if let Some(cached) = self
+            .intrinsics_cache()
+            .get(IntrinsicDimension::MinWidth, height)
+        {
+            return cached;
+        }
+        let value = self.compute_min_intrinsic_width(height);
+        self.intrinsics_cache()
+            .insert(IntrinsicDimension::MinWidth, height, value);
+        value
+    }
+
+    /// The memoized [compute_max_intrinsic_width](RenderBox::compute_max_intrinsic_width).
+    fn get_max_intrinsic_width(&mut self, height: f32) -> f32 {
+        if let Some(cached) = self
+            .intrinsics_cache()
+            .get(IntrinsicDimension::MaxWidth, height)
+        {
+            return cached;
+        }
+        let value = self.compute_max_intrinsic_width(height);
+        self.intrinsics_cache()
+            .insert(IntrinsicDimension::MaxWidth, height, value);
+        value
+    }
+
+    /// The memoized [compute_min_intrinsic_height](RenderBox::compute_min_intrinsic_height).
+    fn get_min_intrinsic_height(&mut self, width: f32) -> f32 {
+        if let Some(cached) = self
+            .intrinsics_cache()
+            .get(IntrinsicDimension::MinHeight, width)
+        {
+            return cached;
+        }
+        let value = self.compute_min_intrinsic_height(width);
+        self.intrinsics_cache()
+            .insert(IntrinsicDimension::MinHeight, width, value);
+        value
+    }
+
+    /// The memoized [compute_max_intrinsic_height](RenderBox::compute_max_intrinsic_height).
+    fn get_max_intrinsic_height(&mut self, width: f32) -> f32 {
+        if let Some(cached) = self
+            .intrinsics_cache()
+            .get(IntrinsicDimension::MaxHeight, width)
+        {
+            return cached;
+        }
+        let value = self.compute_max_intrinsic_height(width);
+        self.intrinsics_cache()
+            .insert(IntrinsicDimension::MaxHeight, width, value);
+        value
+    }
+
+    /// Marks this box as needing layout, invalidating its intrinsic-
+    /// dimension cache in the process since a stale cached result is
+    /// otherwise indistinguishable from a valid one. Box types don't yet
+    /// have to hook into the [crate::rendering::PipelineOwner] dirty-list
+    /// protocol to use this; they can call it directly whenever state that
+    /// affects their size changes (e.g. gaining or losing a child).
+    fn mark_needs_layout(&mut self) {
+        self.intrinsics_cache().clear();
+    }
+
     /// Override this method to handle pointer events that hit this render
     /// object.
     ///
@@ -95,12 +264,47 @@ pub trait RenderBox {
     ///   // ... handle the event ...
     /// }
     /// ```
-    fn handle_event(&mut self, event: PointEvent, entry: Box<HitTestEntry>) {}
+    fn handle_event(&mut self, event: PointerEvent, entry: Box<BoxHitTestEntry>) {
+        let _ = (event, entry);
+    }
 }
 
+/// Adapts any [RenderBox] into a [RenderObject], so it can be adopted as a
+/// child, take part in a [super::PipelineOwner]'s relayout-boundary dirty
+/// list, and carry a [BoxParentData] the way any other `RenderObject` would
+/// — the one place the box-specific world (object-safe, used throughout as
+/// `Rc<RefCell<dyn RenderBox>>`) and the generic `RenderObject` world (dirty
+/// tracking, `adopt_child`, `ParentData`) actually meet.
+///
+/// `RenderObject::perform_layout` takes `&self`, since the trait has no way
+/// to know whether a given render object needs interior mutability; a
+/// [RenderBox], on the other hand, always does its layout work through
+/// `&mut self`. [inner] is therefore kept behind a [RefCell] so this impl
+/// can satisfy both signatures honestly, instead of silently discarding the
+/// layout `R` computes.
 #[derive(Debug)]
 pub struct RenderBoxObject<R: RenderBox> {
-    inner: R,
+    inner: RefCell<R>,
+    render_data: RenderObjectData<BoxConstraints>,
+    parent_data: Option<BoxParentData>,
+}
+
+impl<R: RenderBox> RenderBoxObject<R> {
+    pub fn new(inner: R) -> Self {
+        RenderBoxObject {
+            inner: RefCell::new(inner),
+            render_data: RenderObjectData::new(),
+            parent_data: None,
+        }
+    }
+
+    pub fn inner(&self) -> Ref<'_, R> {
+        self.inner.borrow()
+    }
+
+    pub fn inner_mut(&self) -> RefMut<'_, R> {
+        self.inner.borrow_mut()
+    }
 }
 
 impl<R> RenderObject for RenderBoxObject<R>
@@ -108,4 +312,455 @@ where
     R: RenderBox,
 {
     type Constraints = BoxConstraints;
+    type ParentData = BoxParentData;
+
+    fn render_data(&self) -> &RenderObjectData<Self::Constraints> {
+        &self.render_data
+    }
+
+    fn render_data_mut(&mut self) -> &mut RenderObjectData<Self::Constraints> {
+        &mut self.render_data
+    }
+
+    fn parent_data(&self) -> Option<&Self::ParentData> {
+        self.parent_data.as_ref()
+    }
+
+    fn parent_data_mut(&mut self) -> Option<&mut Self::ParentData> {
+        self.parent_data.as_mut()
+    }
+
+    fn set_parent_data(&mut self, data: Option<Self::ParentData>) {
+        self.parent_data = data;
+    }
+
+    fn perform_layout(&self, constraints: &BoxConstraints) {
+        self.inner.borrow_mut().perform_layout(constraints);
+    }
+}
+
+/// A [RenderBox] with a single optional child that forwards the whole box
+/// protocol to it unchanged: layout, hit-testing, and event handling.
+///
+/// This is the base most single-child box wrappers (padding, opacity,
+/// alignment, ...) should build on, so that only the handful of methods a
+/// wrapper actually customizes need overriding; everything else is already
+/// correct by forwarding to the child.
+#[derive(Debug, Default)]
+pub struct RenderProxyBox {
+    size: Size,
+    child: Option<Rc<RefCell<dyn RenderBox>>>,
+    intrinsics: IntrinsicCache,
+}
+
+impl RenderProxyBox {
+    pub fn new() -> Self {
+        RenderProxyBox {
+            size: Size::zero(),
+            child: None,
+            intrinsics: IntrinsicCache::new(),
+        }
+    }
+
+    pub fn child(&self) -> Option<&Rc<RefCell<dyn RenderBox>>> {
+        self.child.as_ref()
+    }
+
+    pub fn set_child(&mut self, child: Option<Rc<RefCell<dyn RenderBox>>>) {
+        self.child = child;
+        self.mark_needs_layout();
+    }
+}
+
+impl RenderBox for RenderProxyBox {
+    fn size(&self) -> Size {
+        self.size
+    }
+
+    fn set_size(&mut self, size: Size) {
+        self.size = size;
+    }
+
+    /// Lays the child out with the same constraints this box was given
+    /// (`parent_uses_size = true`, since this box's size is just whatever
+    /// the child chose), and adopts the child's resulting size. With no
+    /// child, shrinks to the smallest size the constraints allow.
+    fn perform_layout(&mut self, constraints: &BoxConstraints) {
+        let size = match &self.child {
+            Some(child) => {
+                let mut child_ref = child.borrow_mut();
+                child_ref.layout(constraints, true);
+                child_ref.size()
+            }
+            None => constraints.smallest(),
+        };
+        self.set_size(size);
+    }
+
+    /// This box paints nothing of its own, so it is only ever hit via its
+    /// child; see [hit_test_children](RenderBox::hit_test_children).
+    fn hit_test_self(&mut self, _pos: Offset) -> bool {
+        false
+    }
+
+    /// Forwards the hit test to the child, which occupies this box's exact
+    /// bounds (hence the zero offset).
+    fn hit_test_children(&mut self, result: &mut BoxHitTestResult, pos: Offset) -> bool {
+        match &self.child {
+            Some(child) => {
+                let child_handle = Rc::clone(child);
+                result.add_with_paint_offset(Offset::zero(), pos, |result, transformed| {
+                    child_handle
+                        .borrow_mut()
+                        .hit_test(&child_handle, result, transformed)
+                })
+            }
+            None => false,
+        }
+    }
+
+    /// Forwards the event to the child, if any.
+    fn handle_event(&mut self, event: PointerEvent, entry: Box<BoxHitTestEntry>) {
+        if let Some(child) = &self.child {
+            child.borrow_mut().handle_event(event, entry);
+        }
+    }
+
+    fn intrinsics_cache(&mut self) -> &mut IntrinsicCache {
+        &mut self.intrinsics
+    }
+
+    fn compute_min_intrinsic_width(&mut self, height: f32) -> f32 {
+        match &self.child {
+            Some(child) => child.borrow_mut().get_min_intrinsic_width(height),
+            None => 0.0,
+        }
+    }
+
+    fn compute_max_intrinsic_width(&mut self, height: f32) -> f32 {
+        match &self.child {
+            Some(child) => child.borrow_mut().get_max_intrinsic_width(height),
+            None => 0.0,
+        }
+    }
+
+    fn compute_min_intrinsic_height(&mut self, width: f32) -> f32 {
+        match &self.child {
+            Some(child) => child.borrow_mut().get_min_intrinsic_height(width),
+            None => 0.0,
+        }
+    }
+
+    fn compute_max_intrinsic_height(&mut self, width: f32) -> f32 {
+        match &self.child {
+            Some(child) => child.borrow_mut().get_max_intrinsic_height(width),
+            None => 0.0,
+        }
+    }
+}
+
+/// A [RenderProxyBox] that can make itself (and, unlike most proxies, its
+/// subtree) invisible without removing it from the tree: painting is
+/// skipped once [opacity] reaches zero, but the child continues to
+/// participate in layout and, per the [RenderBox::hit_test] contract, still
+/// responds to hit-testing even while fully transparent.
+#[derive(Debug)]
+pub struct RenderOpacity {
+    proxy: RenderProxyBox,
+    opacity: f32,
+}
+
+impl RenderOpacity {
+    pub fn new(opacity: f32) -> Self {
+        RenderOpacity {
+            proxy: RenderProxyBox::new(),
+            opacity,
+        }
+    }
+
+    pub fn opacity(&self) -> f32 {
+        self.opacity
+    }
+
+    pub fn set_opacity(&mut self, opacity: f32) {
+        self.opacity = opacity;
+    }
+
+    pub fn set_child(&mut self, child: Option<Rc<RefCell<dyn RenderBox>>>) {
+        self.proxy.set_child(child);
+    }
+
+    /// Whether the paint phase should visit this subtree at all. Hit
+    /// testing is unaffected by this and always forwards to the child
+    /// regardless of opacity.
+    pub fn should_paint(&self) -> bool {
+        self.opacity > 0.0
+    }
+}
+
+impl RenderBox for RenderOpacity {
+    fn size(&self) -> Size {
+        self.proxy.size()
+    }
+
+    fn set_size(&mut self, size: Size) {
+        self.proxy.set_size(size);
+    }
+
+    fn perform_layout(&mut self, constraints: &BoxConstraints) {
+        self.proxy.perform_layout(constraints);
+    }
+
+    fn hit_test_self(&mut self, pos: Offset) -> bool {
+        self.proxy.hit_test_self(pos)
+    }
+
+    fn hit_test_children(&mut self, result: &mut BoxHitTestResult, pos: Offset) -> bool {
+        self.proxy.hit_test_children(result, pos)
+    }
+
+    fn intrinsics_cache(&mut self) -> &mut IntrinsicCache {
+        self.proxy.intrinsics_cache()
+    }
+
+    fn compute_min_intrinsic_width(&mut self, height: f32) -> f32 {
+        self.proxy.compute_min_intrinsic_width(height)
+    }
+
+    fn compute_max_intrinsic_width(&mut self, height: f32) -> f32 {
+        self.proxy.compute_max_intrinsic_width(height)
+    }
+
+    fn compute_min_intrinsic_height(&mut self, width: f32) -> f32 {
+        self.proxy.compute_min_intrinsic_height(width)
+    }
+
+    fn compute_max_intrinsic_height(&mut self, width: f32) -> f32 {
+        self.proxy.compute_max_intrinsic_height(width)
+    }
+}
+
+/// A [RenderProxyBox] that imposes additional constraints on its child
+/// before forwarding layout, via [BoxConstraints::enforce]. Used to tighten
+/// (e.g. force an exact size) or loosen (e.g. cap a maximum size) whatever
+/// constraints this box itself receives.
+#[derive(Debug)]
+pub struct RenderConstrainedBox {
+    proxy: RenderProxyBox,
+    additional_constraints: BoxConstraints,
+}
+
+impl RenderConstrainedBox {
+    pub fn new(additional_constraints: BoxConstraints) -> Self {
+        RenderConstrainedBox {
+            proxy: RenderProxyBox::new(),
+            additional_constraints,
+        }
+    }
+
+    pub fn additional_constraints(&self) -> BoxConstraints {
+        self.additional_constraints
+    }
+
+    pub fn set_additional_constraints(&mut self, additional_constraints: BoxConstraints) {
+        self.additional_constraints = additional_constraints;
+    }
+
+    pub fn set_child(&mut self, child: Option<Rc<RefCell<dyn RenderBox>>>) {
+        self.proxy.set_child(child);
+    }
+}
+
+impl RenderBox for RenderConstrainedBox {
+    fn size(&self) -> Size {
+        self.proxy.size()
+    }
+
+    fn set_size(&mut self, size: Size) {
+        self.proxy.set_size(size);
+    }
+
+    fn perform_layout(&mut self, constraints: &BoxConstraints) {
+        let constraints = self.additional_constraints.enforce(constraints);
+        self.proxy.perform_layout(&constraints);
+    }
+
+    fn hit_test_self(&mut self, pos: Offset) -> bool {
+        self.proxy.hit_test_self(pos)
+    }
+
+    fn hit_test_children(&mut self, result: &mut BoxHitTestResult, pos: Offset) -> bool {
+        self.proxy.hit_test_children(result, pos)
+    }
+
+    fn intrinsics_cache(&mut self) -> &mut IntrinsicCache {
+        self.proxy.intrinsics_cache()
+    }
+
+    fn compute_min_intrinsic_width(&mut self, height: f32) -> f32 {
+        self.proxy.compute_min_intrinsic_width(height)
+    }
+
+    fn compute_max_intrinsic_width(&mut self, height: f32) -> f32 {
+        self.proxy.compute_max_intrinsic_width(height)
+    }
+
+    fn compute_min_intrinsic_height(&mut self, width: f32) -> f32 {
+        self.proxy.compute_min_intrinsic_height(width)
+    }
+
+    fn compute_max_intrinsic_height(&mut self, width: f32) -> f32 {
+        self.proxy.compute_max_intrinsic_height(width)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn render_box_object_perform_layout_delegates_to_the_wrapped_box() {
+        let object = RenderBoxObject::new(RenderProxyBox::new());
+        let constraints = BoxConstraints::tight(Size::new(40.0, 30.0));
+
+        RenderObject::perform_layout(&object, &constraints);
+
+        assert_eq!(object.inner().size(), Size::new(40.0, 30.0));
+    }
+
+    #[test]
+    fn render_box_object_parent_data_round_trips() {
+        let mut object = RenderBoxObject::new(RenderProxyBox::new());
+        assert!(object.parent_data().is_none());
+
+        let data = BoxParentData {
+            offset: Offset::new(5.0, 6.0),
+        };
+        object.set_parent_data(Some(data));
+
+        assert_eq!(object.parent_data(), Some(&data));
+        object.parent_data_mut().unwrap().offset = Offset::new(1.0, 2.0);
+        assert_eq!(object.parent_data().unwrap().offset, Offset::new(1.0, 2.0));
+    }
+
+    /// A leaf box that always reports the same fixed size, regardless of
+    /// constraints, for exercising proxy boxes without pulling in a second
+    /// real layout algorithm.
+    #[derive(Debug, Default)]
+    struct FixedSizeBox {
+        size: Size,
+        intrinsics: IntrinsicCache,
+    }
+
+    impl RenderBox for FixedSizeBox {
+        fn size(&self) -> Size {
+            self.size
+        }
+
+        fn set_size(&mut self, size: Size) {
+            self.size = size;
+        }
+
+        fn perform_layout(&mut self, _constraints: &BoxConstraints) {
+            self.set_size(self.size);
+        }
+
+        fn hit_test_self(&mut self, _pos: Offset) -> bool {
+            true
+        }
+
+        fn intrinsics_cache(&mut self) -> &mut IntrinsicCache {
+            &mut self.intrinsics
+        }
+    }
+
+    #[test]
+    fn proxy_with_no_child_shrinks_to_smallest_allowed_size() {
+        let mut proxy = RenderProxyBox::new();
+        proxy.perform_layout(&BoxConstraints::loose(Size::new(100.0, 100.0)));
+        assert_eq!(proxy.size(), Size::zero());
+    }
+
+    #[test]
+    fn proxy_adopts_its_childs_size_and_forwards_hit_tests() {
+        let child: Rc<RefCell<dyn RenderBox>> = Rc::new(RefCell::new(FixedSizeBox {
+            size: Size::new(30.0, 20.0),
+            intrinsics: IntrinsicCache::new(),
+        }));
+        let mut proxy = RenderProxyBox::new();
+        proxy.set_child(Some(Rc::clone(&child)));
+        proxy.perform_layout(&BoxConstraints::loose(Size::new(100.0, 100.0)));
+
+        assert_eq!(proxy.size(), Size::new(30.0, 20.0));
+
+        let mut result = BoxHitTestResult::new();
+        assert!(proxy.hit_test_children(&mut result, Offset::new(5.0, 5.0)));
+    }
+
+    #[test]
+    fn opacity_forwards_layout_to_its_proxy_and_tracks_visibility() {
+        let mut opacity = RenderOpacity::new(1.0);
+        opacity.perform_layout(&BoxConstraints::tight(Size::new(10.0, 10.0)));
+        assert_eq!(opacity.size(), Size::new(10.0, 10.0));
+        assert!(opacity.should_paint());
+
+        opacity.set_opacity(0.0);
+        assert!(!opacity.should_paint());
+    }
+
+    #[test]
+    fn constrained_box_enforces_additional_constraints_on_the_child() {
+        let mut constrained =
+            RenderConstrainedBox::new(BoxConstraints::tight(Size::new(50.0, 50.0)));
+        // The incoming constraints would otherwise allow a much smaller size.
+        constrained.perform_layout(&BoxConstraints::loose(Size::new(200.0, 200.0)));
+        assert_eq!(constrained.size(), Size::new(50.0, 50.0));
+    }
+
+    #[test]
+    fn default_hit_test_misses_outside_the_box() {
+        let handle: Rc<RefCell<dyn RenderBox>> = Rc::new(RefCell::new(RenderProxyBox::new()));
+        handle
+            .borrow_mut()
+            .perform_layout(&BoxConstraints::tight(Size::new(10.0, 10.0)));
+
+        let mut result = BoxHitTestResult::new();
+        let hit = handle
+            .borrow_mut()
+            .hit_test(&handle, &mut result, Offset::new(50.0, 50.0));
+
+        assert!(!hit);
+        assert!(result.path().is_empty());
+    }
+
+    #[test]
+    fn default_hit_test_records_an_entry_when_self_is_hit() {
+        let handle: Rc<RefCell<dyn RenderBox>> = Rc::new(RefCell::new(FixedSizeBox {
+            size: Size::new(10.0, 10.0),
+            intrinsics: IntrinsicCache::new(),
+        }));
+
+        let mut result = BoxHitTestResult::new();
+        let hit = handle
+            .borrow_mut()
+            .hit_test(&handle, &mut result, Offset::new(5.0, 5.0));
+
+        assert!(hit);
+        assert_eq!(result.path().len(), 1);
+        assert_eq!(result.path()[0].local_position, Offset::new(5.0, 5.0));
+    }
+
+    #[test]
+    fn default_handle_event_is_a_no_op() {
+        let mut proxy = RenderProxyBox::new();
+        proxy.handle_event(
+            PointerEvent::Down(Offset::zero()),
+            Box::new(BoxHitTestEntry {
+                target: Rc::new(RefCell::new(RenderProxyBox::new())),
+                local_position: Offset::zero(),
+            }),
+        );
+        // Nothing to assert beyond "doesn't panic": with no child there is
+        // nothing to forward to, so the event is discarded.
+    }
 }
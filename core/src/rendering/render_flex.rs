@@ -0,0 +1,417 @@
+use std::{cell::RefCell, rc::Rc};
+
+use stretch::geometry::Size as StretchSize;
+use stretch::style::{AlignItems, Dimension, FlexDirection, JustifyContent, Style};
+
+use super::{BoxHitTestResult, IntrinsicCache, ParentData, RenderBox};
+use crate::constraints::BoxConstraints;
+use crate::geometry::{Offset, Size};
+
+/// How a [RenderFlex] child's `flex_basis` (its size along the main axis
+/// before flexible growing/shrinking is applied) is determined.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FlexBasis {
+    /// Use the child's own intrinsic/natural size.
+    Auto,
+    /// An exact main-axis extent, in logical pixels.
+    Points(f32),
+    /// A main-axis extent relative to the flex container's own main-axis
+    /// extent.
+    Percent(f32),
+}
+
+impl FlexBasis {
+    fn to_dimension(self) -> Dimension {
+        match self {
+            FlexBasis::Auto => Dimension::Auto,
+            FlexBasis::Points(value) => Dimension::Points(value),
+            FlexBasis::Percent(value) => Dimension::Percent(value),
+        }
+    }
+}
+
+/// Parent data a [RenderFlex] attaches to each of its children: where to
+/// paint it (as with [BoxParentData](super::BoxParentData)), plus the flex
+/// factor and basis that drove its main-axis size.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FlexParentData {
+    pub offset: Offset,
+    pub flex: f32,
+    pub flex_basis: FlexBasis,
+}
+
+impl ParentData for FlexParentData {}
+
+impl Default for FlexParentData {
+    fn default() -> Self {
+        FlexParentData {
+            offset: Offset::zero(),
+            flex: 0.0,
+            flex_basis: FlexBasis::Auto,
+        }
+    }
+}
+
+/// A multi-child [RenderBox] that lays its children out along a main axis
+/// using the [stretch] flexbox engine, giving users a working Row/Column
+/// without this crate having to implement the flex algorithm itself.
+#[derive(Debug)]
+pub struct RenderFlex {
+    size: Size,
+    children: Vec<(Rc<RefCell<dyn RenderBox>>, FlexParentData)>,
+    direction: FlexDirection,
+    justify_content: JustifyContent,
+    align_items: AlignItems,
+    intrinsics: IntrinsicCache,
+}
+
+impl RenderFlex {
+    pub fn new(direction: FlexDirection) -> Self {
+        RenderFlex {
+            size: Size::zero(),
+            children: Vec::new(),
+            direction,
+            justify_content: JustifyContent::FlexStart,
+            align_items: AlignItems::Stretch,
+            intrinsics: IntrinsicCache::new(),
+        }
+    }
+
+    pub fn direction(&self) -> FlexDirection {
+        self.direction
+    }
+
+    pub fn set_direction(&mut self, direction: FlexDirection) {
+        self.direction = direction;
+        self.mark_needs_layout();
+    }
+
+    pub fn justify_content(&self) -> JustifyContent {
+        self.justify_content
+    }
+
+    pub fn set_justify_content(&mut self, justify_content: JustifyContent) {
+        self.justify_content = justify_content;
+        self.mark_needs_layout();
+    }
+
+    pub fn align_items(&self) -> AlignItems {
+        self.align_items
+    }
+
+    pub fn set_align_items(&mut self, align_items: AlignItems) {
+        self.align_items = align_items;
+        self.mark_needs_layout();
+    }
+
+    /// Appends a child with the given flex factor and basis. A `flex` of
+    /// `0.0` means the child only ever takes its `flex_basis`, never
+    /// growing or shrinking to fill leftover space.
+    pub fn add_child(&mut self, child: Rc<RefCell<dyn RenderBox>>, flex: f32, flex_basis: FlexBasis) {
+        self.children.push((
+            child,
+            FlexParentData {
+                offset: Offset::zero(),
+                flex,
+                flex_basis,
+            },
+        ));
+        self.mark_needs_layout();
+    }
+
+    fn axis_dimension(min: f32, max: f32) -> (Dimension, Dimension, Dimension) {
+        let min_size = if min > 0.0 {
+            Dimension::Points(min)
+        } else {
+            Dimension::Auto
+        };
+        let max_size = if max.is_finite() {
+            Dimension::Points(max)
+        } else {
+            Dimension::Auto
+        };
+        let size = if min == max {
+            Dimension::Points(min)
+        } else {
+            Dimension::Auto
+        };
+        (size, min_size, max_size)
+    }
+}
+
+impl RenderBox for RenderFlex {
+    fn size(&self) -> Size {
+        self.size
+    }
+
+    fn set_size(&mut self, size: Size) {
+        self.size = size;
+    }
+
+    /// Translates the incoming [BoxConstraints] into a `stretch::style::Style`,
+    /// builds a `stretch` node tree mirroring `self.children`, asks `stretch`
+    /// to compute the flex layout, then reads each child's resulting rect
+    /// back out: the offset is written into the child's [FlexParentData],
+    /// and the child itself is laid out with the tight constraints `stretch`
+    /// decided on.
+    ///
+    /// A [FlexBasis::Auto] child's basis is resolved by querying its
+    /// intrinsic main-axis extent (`stretch` itself has no notion of an
+    /// `xui` child's intrinsics, so this has to happen up front rather than
+    /// through a `stretch` measure function), at the cross-axis extent this
+    /// container is being asked to lay out at.
+    fn perform_layout(&mut self, constraints: &BoxConstraints) {
+        let (width, min_width, max_width) =
+            RenderFlex::axis_dimension(constraints.min_width, constraints.max_width);
+        let (height, min_height, max_height) =
+            RenderFlex::axis_dimension(constraints.min_height, constraints.max_height);
+
+        let direction = self.direction;
+        let cross_axis_extent = match direction {
+            FlexDirection::Row | FlexDirection::RowReverse => {
+                if constraints.max_height.is_finite() {
+                    constraints.max_height
+                } else {
+                    f32::INFINITY
+                }
+            }
+            FlexDirection::Column | FlexDirection::ColumnReverse => {
+                if constraints.max_width.is_finite() {
+                    constraints.max_width
+                } else {
+                    f32::INFINITY
+                }
+            }
+        };
+
+        let mut stretch = stretch::node::Stretch::new();
+
+        let child_nodes: Vec<stretch::node::Node> = self
+            .children
+            .iter()
+            .map(|(child, data)| {
+                // `stretch` only measures a flex item's content size from its
+                // `size`, not its `flex_basis` (`flex_basis` just feeds the
+                // space-distribution algorithm); an `Auto`-basis item whose
+                // `size` is left at its own `Auto` default therefore still
+                // reports zero content size. So a resolved intrinsic extent
+                // is written to both.
+                let main_axis_size = match data.flex_basis {
+                    FlexBasis::Auto => {
+                        let mut child_ref = child.borrow_mut();
+                        let intrinsic_extent = match direction {
+                            FlexDirection::Row | FlexDirection::RowReverse => {
+                                child_ref.get_max_intrinsic_width(cross_axis_extent)
+                            }
+                            FlexDirection::Column | FlexDirection::ColumnReverse => {
+                                child_ref.get_max_intrinsic_height(cross_axis_extent)
+                            }
+                        };
+                        Some(Dimension::Points(intrinsic_extent))
+                    }
+                    _ => None,
+                };
+                let flex_basis = main_axis_size.unwrap_or_else(|| data.flex_basis.to_dimension());
+                let size = match (direction, main_axis_size) {
+                    (FlexDirection::Row | FlexDirection::RowReverse, Some(width)) => StretchSize {
+                        width,
+                        height: Dimension::Auto,
+                    },
+                    (FlexDirection::Column | FlexDirection::ColumnReverse, Some(height)) => {
+                        StretchSize {
+                            width: Dimension::Auto,
+                            height,
+                        }
+                    }
+                    _ => StretchSize {
+                        width: Dimension::Auto,
+                        height: Dimension::Auto,
+                    },
+                };
+                stretch
+                    .new_node(
+                        Style {
+                            flex_grow: data.flex,
+                            flex_shrink: data.flex,
+                            flex_basis,
+                            size,
+                            ..Default::default()
+                        },
+                        vec![],
+                    )
+                    .expect("a leaf stretch node can only fail to construct on an invalid tree")
+            })
+            .collect();
+
+        let root = stretch
+            .new_node(
+                Style {
+                    flex_direction: self.direction,
+                    justify_content: self.justify_content,
+                    align_items: self.align_items,
+                    size: StretchSize {
+                        width,
+                        height,
+                    },
+                    min_size: StretchSize {
+                        width: min_width,
+                        height: min_height,
+                    },
+                    max_size: StretchSize {
+                        width: max_width,
+                        height: max_height,
+                    },
+                    ..Default::default()
+                },
+                child_nodes.clone(),
+            )
+            .expect("a stretch node can only fail to construct on an invalid tree");
+
+        stretch
+            .compute_layout(root, StretchSize::undefined())
+            .expect("stretch layout has no fallible user measure functions in this crate");
+
+        let root_layout = stretch
+            .layout(root)
+            .expect("root's layout was just computed above");
+        self.set_size(Size::new(root_layout.size.width, root_layout.size.height));
+
+        for ((child, data), node) in self.children.iter_mut().zip(child_nodes.iter()) {
+            let layout = stretch
+                .layout(*node)
+                .expect("every child's layout was computed by compute_layout above");
+            data.offset = Offset::new(layout.location.x, layout.location.y);
+            let child_constraints =
+                BoxConstraints::tight(Size::new(layout.size.width, layout.size.height));
+            child.borrow_mut().layout(&child_constraints, false);
+        }
+    }
+
+    fn hit_test_self(&mut self, _pos: Offset) -> bool {
+        false
+    }
+
+    /// Hit-tests children back-to-front (i.e. in reverse of the order they
+    /// were added), so a later, visually-on-top child wins ties.
+    fn hit_test_children(&mut self, result: &mut BoxHitTestResult, pos: Offset) -> bool {
+        for (child, data) in self.children.iter().rev() {
+            let child_handle = Rc::clone(child);
+            let hit = result.add_with_paint_offset(data.offset, pos, |result, transformed| {
+                child_handle
+                    .borrow_mut()
+                    .hit_test(&child_handle, result, transformed)
+            });
+            if hit {
+                return true;
+            }
+        }
+        false
+    }
+
+    fn intrinsics_cache(&mut self) -> &mut IntrinsicCache {
+        &mut self.intrinsics
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A leaf box with a fixed intrinsic size, just large enough to give
+    /// `stretch` something non-trivial to lay out along the main axis.
+    /// Always reports itself as hit, so overlapping children can be used to
+    /// test hit-test ordering in isolation from `stretch`'s own geometry.
+    #[derive(Debug)]
+    struct FixedSizeBox {
+        size: Size,
+        intrinsics: IntrinsicCache,
+    }
+
+    impl RenderBox for FixedSizeBox {
+        fn size(&self) -> Size {
+            self.size
+        }
+
+        fn set_size(&mut self, size: Size) {
+            self.size = size;
+        }
+
+        fn perform_layout(&mut self, _constraints: &BoxConstraints) {}
+
+        fn hit_test_self(&mut self, _pos: Offset) -> bool {
+            true
+        }
+
+        fn compute_max_intrinsic_width(&mut self, _height: f32) -> f32 {
+            self.size.width
+        }
+
+        fn compute_max_intrinsic_height(&mut self, _width: f32) -> f32 {
+            self.size.height
+        }
+
+        fn intrinsics_cache(&mut self) -> &mut IntrinsicCache {
+            &mut self.intrinsics
+        }
+    }
+
+    fn fixed_child(width: f32, height: f32) -> Rc<RefCell<dyn RenderBox>> {
+        Rc::new(RefCell::new(FixedSizeBox {
+            size: Size::new(width, height),
+            intrinsics: IntrinsicCache::new(),
+        }))
+    }
+
+    #[test]
+    fn row_with_tight_constraints_sizes_to_exactly_those_constraints() {
+        let mut flex = RenderFlex::new(FlexDirection::Row);
+        flex.add_child(fixed_child(10.0, 10.0), 1.0, FlexBasis::Points(10.0));
+        flex.add_child(fixed_child(10.0, 10.0), 1.0, FlexBasis::Points(10.0));
+
+        flex.perform_layout(&BoxConstraints::tight(Size::new(100.0, 40.0)));
+
+        assert_eq!(flex.size(), Size::new(100.0, 40.0));
+    }
+
+    #[test]
+    fn equal_flex_children_split_the_available_main_axis_space_evenly() {
+        let mut flex = RenderFlex::new(FlexDirection::Row);
+        flex.add_child(fixed_child(10.0, 10.0), 1.0, FlexBasis::Points(0.0));
+        flex.add_child(fixed_child(10.0, 10.0), 1.0, FlexBasis::Points(0.0));
+
+        flex.perform_layout(&BoxConstraints::tight(Size::new(100.0, 40.0)));
+
+        assert_eq!(flex.children[0].1.offset, Offset::new(0.0, 0.0));
+        assert_eq!(flex.children[1].1.offset, Offset::new(50.0, 0.0));
+    }
+
+    #[test]
+    fn flex_basis_auto_sizes_to_the_childs_intrinsic_extent() {
+        let mut flex = RenderFlex::new(FlexDirection::Row);
+        flex.add_child(fixed_child(80.0, 10.0), 0.0, FlexBasis::Auto);
+
+        flex.perform_layout(&BoxConstraints::loose(Size::new(500.0, 500.0)));
+
+        assert_eq!(flex.size().width, 80.0);
+    }
+
+    #[test]
+    fn hit_test_children_prefers_the_later_added_child_on_overlap() {
+        let first = fixed_child(10.0, 10.0);
+        let second = fixed_child(10.0, 10.0);
+        let mut flex = RenderFlex::new(FlexDirection::Row);
+        flex.add_child(Rc::clone(&first), 0.0, FlexBasis::Points(10.0));
+        flex.add_child(Rc::clone(&second), 0.0, FlexBasis::Points(10.0));
+        flex.perform_layout(&BoxConstraints::tight(Size::new(20.0, 10.0)));
+
+        // Force an overlap regardless of what stretch actually computed, to
+        // isolate reverse-order traversal from stretch's own layout math.
+        flex.children[0].1.offset = Offset::zero();
+        flex.children[1].1.offset = Offset::zero();
+
+        let mut result = BoxHitTestResult::new();
+        assert!(flex.hit_test_children(&mut result, Offset::new(5.0, 5.0)));
+        assert_eq!(result.path().len(), 1);
+        assert!(Rc::ptr_eq(&result.path()[0].target, &second));
+    }
+}
@@ -0,0 +1,157 @@
+use std::fmt::Debug;
+
+use crate::constraints::{SliverConstraints, SliverGeometry};
+
+/// The sliver-protocol counterpart to [RenderBox](super::RenderBox): a lazy,
+/// viewport-driven render object that consumes [SliverConstraints] and
+/// reports back a [SliverGeometry] instead of a [Size](crate::geometry::Size),
+/// so it can occupy however little of itself is actually in view.
+pub trait RenderSliver: Debug {
+    /// This sliver's geometry, as computed by the most recent layout pass.
+    fn geometry(&self) -> SliverGeometry;
+
+    /// Records the geometry this sliver has chosen for itself. Called by
+    /// [perform_layout](RenderSliver::perform_layout) implementations; not
+    /// normally called directly.
+    fn set_geometry(&mut self, geometry: SliverGeometry);
+
+    /// Computes layout for this sliver given `constraints` from its parent
+    /// viewport. The default forwards straight to
+    /// [perform_layout](RenderSliver::perform_layout).
+    fn layout(&mut self, constraints: &SliverConstraints) {
+        self.perform_layout(constraints);
+    }
+
+    /// Do the work of computing this sliver's geometry and laying out any
+    /// children. Do not call this directly; call
+    /// [layout](RenderSliver::layout) instead.
+    fn perform_layout(&mut self, constraints: &SliverConstraints);
+
+    /// Determines whether this sliver (or one of its descendants) is hit at
+    /// the given sliver-space position, expressed as a distance along the
+    /// main axis from this sliver's leading edge and a distance along the
+    /// cross axis from its cross-axis start, mirroring
+    /// [RenderBox::hit_test](super::RenderBox::hit_test) but in the
+    /// one-dimensional-plus-cross-axis coordinate space slivers use instead
+    /// of a [Rect](crate::geometry::Rect).
+    fn hit_test(&mut self, main_axis_position: f32, cross_axis_position: f32) -> bool {
+        let _ = (main_axis_position, cross_axis_position);
+        false
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constraints::{AxisDirection, GrowthDirection};
+
+    fn sliver_constraints() -> SliverConstraints {
+        SliverConstraints {
+            axis_direction: AxisDirection::Down,
+            growth_direction: GrowthDirection::Forward,
+            scroll_offset: 0.0,
+            preceding_scroll_extent: 0.0,
+            remaining_paint_extent: 100.0,
+            cross_axis_extent: 50.0,
+            viewport_main_axis_extent: 100.0,
+            remaining_cache_extent: 100.0,
+        }
+    }
+
+    /// A sliver with a fixed geometry, reporting hits only within its
+    /// `extent`, just large enough to exercise `RenderSliver`'s default
+    /// `layout`/`hit_test` without pulling in a real multi-child sliver.
+    #[derive(Debug)]
+    struct FixedGeometrySliver {
+        extent: f32,
+        geometry: SliverGeometry,
+    }
+
+    impl RenderSliver for FixedGeometrySliver {
+        fn geometry(&self) -> SliverGeometry {
+            self.geometry
+        }
+
+        fn set_geometry(&mut self, geometry: SliverGeometry) {
+            self.geometry = geometry;
+        }
+
+        fn perform_layout(&mut self, constraints: &SliverConstraints) {
+            self.set_geometry(SliverGeometry::new(
+                self.extent,
+                self.extent.min(constraints.remaining_paint_extent),
+                self.extent,
+            ));
+        }
+
+        fn hit_test(&mut self, main_axis_position: f32, _cross_axis_position: f32) -> bool {
+            main_axis_position >= 0.0 && main_axis_position < self.extent
+        }
+    }
+
+    #[test]
+    fn layout_forwards_to_perform_layout_and_records_the_resulting_geometry() {
+        let mut sliver = FixedGeometrySliver {
+            extent: 40.0,
+            geometry: SliverGeometry::zero(),
+        };
+
+        sliver.layout(&sliver_constraints());
+
+        assert_eq!(sliver.geometry(), SliverGeometry::new(40.0, 40.0, 40.0));
+    }
+
+    #[test]
+    fn layout_clamps_paint_extent_to_what_the_viewport_has_left() {
+        let mut sliver = FixedGeometrySliver {
+            extent: 200.0,
+            geometry: SliverGeometry::zero(),
+        };
+        let mut constraints = sliver_constraints();
+        constraints.remaining_paint_extent = 30.0;
+
+        sliver.layout(&constraints);
+
+        assert_eq!(sliver.geometry(), SliverGeometry::new(200.0, 30.0, 200.0));
+    }
+
+    #[test]
+    fn default_hit_test_misses_everywhere() {
+        // A sliver that doesn't override `hit_test` should behave like
+        // `RenderBox::hit_test_self`'s default: never hit.
+        #[derive(Debug)]
+        struct NeverHitSliver {
+            geometry: SliverGeometry,
+        }
+
+        impl RenderSliver for NeverHitSliver {
+            fn geometry(&self) -> SliverGeometry {
+                self.geometry
+            }
+
+            fn set_geometry(&mut self, geometry: SliverGeometry) {
+                self.geometry = geometry;
+            }
+
+            fn perform_layout(&mut self, _constraints: &SliverConstraints) {}
+        }
+
+        let mut sliver = NeverHitSliver {
+            geometry: SliverGeometry::zero(),
+        };
+        assert!(!sliver.hit_test(0.0, 0.0));
+    }
+
+    #[test]
+    fn custom_hit_test_respects_the_slivers_extent() {
+        let mut sliver = FixedGeometrySliver {
+            extent: 40.0,
+            geometry: SliverGeometry::zero(),
+        };
+
+        assert!(sliver.hit_test(0.0, 10.0));
+        assert!(sliver.hit_test(39.9, 10.0));
+        assert!(!sliver.hit_test(40.0, 10.0));
+        assert!(!sliver.hit_test(-1.0, 10.0));
+    }
+}